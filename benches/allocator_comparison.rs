@@ -0,0 +1,89 @@
+// Benchmark comparing global allocators for the 100 GB streaming scenario
+//
+// Run once per allocator and compare the reported GB/s:
+//   cargo bench --bench allocator_comparison
+//   cargo bench --bench allocator_comparison --features jemalloc
+//   cargo bench --bench allocator_comparison --features mimalloc
+//
+// Set DGEN_BENCH_GB to shorten the run (default 100).
+
+use dgen_data::{generate_data, DataGenerator, GeneratorConfig};
+use std::time::Instant;
+
+const DEFAULT_TOTAL_GB: usize = 100;
+const CHUNK_SIZE: usize = 32 * 1024 * 1024; // 32 MB chunks for streaming
+
+fn allocator_name() -> &'static str {
+    if cfg!(feature = "jemalloc") {
+        "jemalloc"
+    } else if cfg!(feature = "mimalloc") {
+        "mimalloc"
+    } else {
+        "system"
+    }
+}
+
+fn report(label: &str, total: usize, secs: f64) {
+    println!(
+        "{:<28} {:>6} GB in {:>7.2} s: {:>7.2} GB/s",
+        label,
+        total / (1024 * 1024 * 1024),
+        secs,
+        total as f64 / secs / 1e9
+    );
+}
+
+fn main() {
+    let total_gb = std::env::var("DGEN_BENCH_GB")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TOTAL_GB);
+    let total = total_gb * 1024 * 1024 * 1024;
+
+    println!(
+        "ALLOCATOR COMPARISON (global allocator: {})",
+        allocator_name()
+    );
+    println!(
+        "Total: {} GB, chunk: {} MB, threads: {}",
+        total_gb,
+        CHUNK_SIZE / (1024 * 1024),
+        num_cpus::get()
+    );
+    println!();
+
+    // Streaming: one generator, one reused chunk buffer (no per-chunk allocation)
+    let mut gen = DataGenerator::new(GeneratorConfig {
+        size: total,
+        ..Default::default()
+    });
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let start = Instant::now();
+    let mut written = 0;
+    while !gen.is_complete() {
+        written += gen.fill_chunk(&mut buffer);
+    }
+    report(
+        "streaming (reused buffer)",
+        written,
+        start.elapsed().as_secs_f64(),
+    );
+
+    // One-shot: a fresh generate_data() buffer per chunk, freed after each call.
+    // This is where allocator choice matters: each chunk is an alloc + free.
+    let config = GeneratorConfig {
+        size: CHUNK_SIZE,
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let mut written = 0;
+    while written < total {
+        let buf = generate_data(config.clone());
+        written += buf.len();
+    }
+    report(
+        "one-shot (alloc per chunk)",
+        written,
+        start.elapsed().as_secs_f64(),
+    );
+}
@@ -0,0 +1,98 @@
+// Criterion benchmarks for one-shot and streaming generation
+//
+//   cargo bench --bench generation
+//   cargo bench --bench generation -- --save-baseline main   # then compare a branch
+//   cargo bench --bench generation -- --baseline main
+//
+// Each case reports throughput in bytes/s and is parameterized by block_size.
+// For a long-running, human-readable 100 GB run see benches/streaming_throughput.rs
+// (the `streaming-bench` binary).
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dgen_data::{generate_data, DataGenerator, GeneratorConfig};
+use std::hint::black_box;
+use std::time::Duration;
+
+const SIZE: usize = 256 * 1024 * 1024; // 256 MiB per iteration
+const CHUNK_SIZE: usize = 32 * 1024 * 1024; // 32 MiB streaming chunks
+const BLOCK_SIZES: [usize; 3] = [1024 * 1024, 4 * 1024 * 1024, 16 * 1024 * 1024];
+
+fn config(block_size: usize, dedup_factor: usize, compress_factor: usize) -> GeneratorConfig {
+    GeneratorConfig {
+        size: SIZE,
+        dedup_factor,
+        compress_factor,
+        block_size: Some(block_size),
+        seed: Some(42),
+        ..Default::default()
+    }
+}
+
+fn bench_ratios(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate_data");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, dedup, compress) in [
+        ("incompressible", 1, 1),
+        ("compress_2to1", 1, 2),
+        ("dedup_2to1", 2, 1),
+    ] {
+        for block_size in BLOCK_SIZES {
+            group.bench_with_input(
+                BenchmarkId::new(name, block_size / (1024 * 1024)),
+                &config(block_size, dedup, compress),
+                |b, config| b.iter(|| black_box(generate_data(config.clone()))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_streaming_vs_oneshot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming_vs_oneshot");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for block_size in BLOCK_SIZES {
+        let config = config(block_size, 1, 1);
+
+        // One generator, one reused chunk buffer
+        group.bench_with_input(
+            BenchmarkId::new("streaming", block_size / (1024 * 1024)),
+            &config,
+            |b, config| {
+                let mut buffer = vec![0u8; CHUNK_SIZE];
+                b.iter(|| {
+                    let mut gen = DataGenerator::new(config.clone());
+                    while !gen.is_complete() {
+                        gen.fill_chunk(&mut buffer);
+                    }
+                    black_box(&buffer);
+                })
+            },
+        );
+
+        // A fresh generate_data() buffer per chunk
+        group.bench_with_input(
+            BenchmarkId::new("oneshot", block_size / (1024 * 1024)),
+            &GeneratorConfig {
+                size: CHUNK_SIZE,
+                ..config
+            },
+            |b, chunk_config| {
+                b.iter(|| {
+                    for _ in 0..SIZE / CHUNK_SIZE {
+                        black_box(generate_data(chunk_config.clone()));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ratios, bench_streaming_vs_oneshot);
+criterion_main!(benches);
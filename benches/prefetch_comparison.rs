@@ -0,0 +1,54 @@
+// Benchmark comparing parallel fill throughput with and without software prefetch
+//
+// Run both variants and compare the reported GB/s:
+//   cargo bench --bench prefetch_comparison
+//   cargo bench --bench prefetch_comparison --features prefetch
+//
+// Results: none recorded yet. The only run so far was on a 1-CPU VM, where
+// generate_data() takes its sequential path and never prefetches, so both variants
+// measure the same code. Record multi-core numbers here before the `prefetch`
+// feature is described as an optimization.
+
+use dgen_data::{benchmark, GeneratorConfig};
+
+const SIZE: usize = 4 * 1024 * 1024 * 1024; // 4 GB per iteration
+const ITERATIONS: usize = 5;
+
+fn main() {
+    println!(
+        "PREFETCH COMPARISON (prefetch feature {})",
+        if cfg!(feature = "prefetch") {
+            "ENABLED"
+        } else {
+            "disabled"
+        }
+    );
+    println!(
+        "Size: {} GB, iterations: {}, threads: {}",
+        SIZE / (1024 * 1024 * 1024),
+        ITERATIONS,
+        num_cpus::get()
+    );
+    println!();
+
+    for (label, compress_factor) in [("incompressible", 1), ("compress 2:1", 2)] {
+        for block_size in [1024 * 1024, 4 * 1024 * 1024] {
+            let config = GeneratorConfig {
+                size: SIZE,
+                compress_factor,
+                block_size: Some(block_size),
+                ..Default::default()
+            };
+            let result = benchmark(&config, ITERATIONS);
+            println!(
+                "{:<15} block={:>2} MB: {:>7.2} GB/s (min {:.2}, max {:.2}, stddev {:.2})",
+                label,
+                block_size / (1024 * 1024),
+                result.total_gbps,
+                result.min_gbps,
+                result.max_gbps,
+                result.stddev_gbps
+            );
+        }
+    }
+}
@@ -28,6 +28,7 @@ fn benchmark_block_size(block_size: usize) {
         numa_node: None,
         block_size: Some(block_size),
         seed: None,
+        ..Default::default()
     };
 
     let mut gen = DataGenerator::new(config);
@@ -57,6 +58,7 @@ fn benchmark_block_size(block_size: usize) {
             numa_node: None,
             block_size: Some(block_size),
             seed: None,
+            ..Default::default()
         };
 
         let mut gen = DataGenerator::new(config);
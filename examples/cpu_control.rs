@@ -4,8 +4,12 @@
 
 //! Example demonstrating CPU count and NUMA mode control
 
-use dgen_data::{generate_data, GeneratorConfig, NumaMode};
-use std::time::Instant;
+use dgen_data::{benchmark, BenchmarkResult, GeneratorConfig, NumaMode};
+
+/// One warmup and one timed `generate_data()` run
+fn run(config: &GeneratorConfig) -> BenchmarkResult {
+    benchmark(config, 1)
+}
 
 fn main() {
     // Initialize tracing to see configuration info
@@ -26,12 +30,10 @@ fn main() {
         numa_mode: NumaMode::Auto,
         max_threads: None, // Use all cores
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!("   Throughput: {:.2} GB/s\n", throughput);
 
     // Example 2: Limit to 4 threads
@@ -45,12 +47,10 @@ fn main() {
         numa_mode: NumaMode::Auto,
         max_threads: Some(4),
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!("   Throughput: {:.2} GB/s\n", throughput);
 
     // Example 3: Single-threaded
@@ -64,12 +64,10 @@ fn main() {
         numa_mode: NumaMode::Auto,
         max_threads: Some(1),
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!(
         "   Throughput: {:.2} GB/s (baseline per-core)\n",
         throughput
@@ -88,12 +86,10 @@ fn main() {
         numa_mode: NumaMode::Auto,
         max_threads: None,
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!("   Throughput: {:.2} GB/s\n", throughput);
 
     // Example 5: Force NUMA mode (for testing on UMA)
@@ -107,12 +103,10 @@ fn main() {
         numa_mode: NumaMode::Force,
         max_threads: None,
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!("   Throughput: {:.2} GB/s\n", throughput);
 
     // Example 6: Disable NUMA mode
@@ -126,12 +120,10 @@ fn main() {
         numa_mode: NumaMode::Disabled,
         max_threads: None,
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let _data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
+    let throughput = run(&config).total_gbps;
     println!("   Throughput: {:.2} GB/s\n", throughput);
 
     println!("\n=== Combined Configuration ===\n");
@@ -147,13 +139,14 @@ fn main() {
         numa_mode: NumaMode::Force,
         max_threads: Some(8),
         numa_node: None,
+        ..Default::default()
     };
 
-    let start = Instant::now();
-    let data = generate_data(config);
-    let elapsed = start.elapsed();
-    let throughput = (size as f64) / elapsed.as_secs_f64() / 1e9;
-    println!("   Generated {} bytes", data.len());
-    println!("   Throughput: {:.2} GB/s", throughput);
+    let result = run(&config);
+    println!("   Generated {} bytes", result.bytes_per_iteration);
+    println!(
+        "   Throughput: {:.2} GB/s ({} threads)",
+        result.total_gbps, result.threads
+    );
     println!("   Note: Lower throughput due to compression overhead\n");
 }
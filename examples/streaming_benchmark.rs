@@ -4,130 +4,130 @@
 
 //! Streaming benchmark - generates data in chunks like real storage workloads
 //!
-//! This measures pure generation speed without Python overhead.
-//! Use this to verify generation can exceed storage bandwidth (80+ GB/s target).
-
-use dgen_data::{DataGenerator, GeneratorConfig, NumaMode};
-use std::time::Instant;
+//! This measures pure generation speed without Python overhead, across chunk
+//! sizes and NUMA modes. Use it to verify generation can exceed storage bandwidth
+//! (80+ GB/s target) and to pick settings for a machine.
+//!
+//! Usage: `cargo run --release --example streaming_benchmark -- [size_gb] [iterations]`
+//! (defaults: 100 GB per run, 3 timed iterations after one warmup)
 
-fn format_throughput(bytes: usize, duration_secs: f64) -> String {
-    let gb_per_sec = (bytes as f64) / duration_secs / 1e9;
-    format!("{:.2} GB/s", gb_per_sec)
-}
+use dgen_data::{benchmark_streaming, BenchmarkResult, GeneratorConfig, NumaMode};
 
-fn format_size(bytes: usize) -> String {
-    if bytes >= 1024 * 1024 * 1024 {
-        format!("{} GB", bytes / (1024 * 1024 * 1024))
-    } else if bytes >= 1024 * 1024 {
-        format!("{} MB", bytes / (1024 * 1024))
-    } else {
-        format!("{} bytes", bytes)
-    }
-}
+const MB: usize = 1024 * 1024;
+const TARGET_GBPS: f64 = 80.0;
 
 fn main() {
     // Initialize logging
     tracing_subscriber::fmt().with_env_filter("info").init();
 
+    let mut args = std::env::args().skip(1);
+    let size_gb: usize = args.next().map_or(100, |s| s.parse().expect("size_gb"));
+    let iterations: usize = args.next().map_or(3, |s| s.parse().expect("iterations"));
+    let size = size_gb * 1024 * MB;
+
     println!("\n=================================================================");
     println!("STREAMING DATA GENERATION BENCHMARK");
     println!("=================================================================");
-    println!("Goal: Measure pure generation speed for storage workloads");
-    println!("Target: > 80 GB/s (to not bottleneck storage)");
+    println!("Total size per run: {} GB", size_gb);
+    println!("Iterations: {} (plus 1 warmup)", iterations);
+    println!("Target: > {} GB/s (to not bottleneck storage)", TARGET_GBPS);
+    #[cfg(feature = "numa")]
+    if let Some(topology) = dgen_data::numa::detected_topology() {
+        println!("\n{}", topology);
+    }
     println!("=================================================================\n");
 
-    // Test configuration
-    let total_size = 100 * 1024 * 1024 * 1024; // 100 GB
-    let chunk_size = 64 * 1024 * 1024; // 64 MB chunks
-    let iterations = 3;
-
-    println!("Configuration:");
-    println!("  Total size per run: {}", format_size(total_size));
-    println!("  Chunk size: {}", format_size(chunk_size));
-    println!("  Iterations: {}", iterations);
-    println!("  Threads: All available (auto-detect)");
-    println!("  NUMA mode: Auto\n");
-
-    let mut run_times = Vec::new();
-
-    for run in 1..=iterations {
-        // Create streaming generator
+    let all_cores = GeneratorConfig {
+        size,
+        numa_mode: NumaMode::Auto,
+        ..Default::default()
+    };
+    let half_threads = GeneratorConfig {
+        max_threads: Some((all_cores.generation_threads() / 2).max(1)),
+        numa_mode: NumaMode::Force,
+        ..all_cores.clone()
+    };
+
+    let mut runs: Vec<(String, GeneratorConfig, usize)> = [4, 8, 16, 32, 64, 128, 256, 512]
+        .iter()
+        .map(|&chunk_mb| {
+            let name = format!("NUMA auto, {} MB chunks", chunk_mb);
+            (name, all_cores.clone(), chunk_mb * MB)
+        })
+        .collect();
+    for (name, numa_mode) in [
+        ("NUMA disabled, 64 MB chunks", NumaMode::Disabled),
+        ("NUMA force, 64 MB chunks", NumaMode::Force),
+    ] {
         let config = GeneratorConfig {
-            size: total_size,
-            dedup_factor: 1,
-            compress_factor: 1,
-            block_size: None,
-            seed: None,
-            numa_mode: NumaMode::Auto,
-            max_threads: None, // Use all cores
-            numa_node: None,
+            numa_mode,
+            ..all_cores.clone()
+        };
+        runs.push((name.to_string(), config, 64 * MB));
+    }
+    runs.push((
+        "NUMA force, half threads, 64 MB chunks".to_string(),
+        half_threads,
+        64 * MB,
+    ));
+
+    let results: Vec<(String, BenchmarkResult)> = runs
+        .into_iter()
+        .map(|(name, config, chunk_size)| {
+            let result = benchmark_streaming(&config, chunk_size, iterations);
+            println!(
+                "{:<40} {:>8.2} GB/s (min {:.2}, max {:.2}, {} threads)",
+                name, result.total_gbps, result.min_gbps, result.max_gbps, result.threads
+            );
+            (name, result)
+        })
+        .collect();
+
+    let best = results
+        .iter()
+        .map(|(_, r)| r.total_gbps)
+        .fold(0.0f64, f64::max);
+
+    println!("\n{}", "-".repeat(84));
+    println!(
+        "{:<40} | {:>12} | {:>13} | {:>9}",
+        "Configuration", "Throughput", "Per thread", "% of Best"
+    );
+    println!("{}", "-".repeat(84));
+    for (name, result) in &results {
+        let marker = if result.total_gbps == best {
+            " ← BEST"
+        } else {
+            ""
         };
-
-        let mut generator = DataGenerator::new(config);
-
-        // Pre-allocate reusable buffer (zero-copy - reused across chunks)
-        let mut buffer = vec![0u8; chunk_size];
-
-        let start = Instant::now();
-        let mut total_generated = 0;
-        let mut chunks = 0;
-
-        // Stream through all data
-        while !generator.is_complete() {
-            let nbytes = generator.fill_chunk(&mut buffer);
-            if nbytes == 0 {
-                break;
-            }
-            total_generated += nbytes;
-            chunks += 1;
-
-            // In real usage, you would write buffer[..nbytes] to storage here
-            // e.g., file.write_all(&buffer[..nbytes])?;
-        }
-
-        let elapsed = start.elapsed();
-        let elapsed_secs = elapsed.as_secs_f64();
-        let throughput = format_throughput(total_generated, elapsed_secs);
-
-        run_times.push(elapsed_secs);
-
         println!(
-            "Run {:02}: {:.4}s | {} | {} chunks",
-            run, elapsed_secs, throughput, chunks
+            "{:<40} | {:>7.2} GB/s | {:>8.2} GB/s | {:>8.1}%{}",
+            name,
+            result.total_gbps,
+            result.per_core_gbps,
+            result.total_gbps / best * 100.0,
+            marker
         );
     }
-
-    // Calculate statistics
-    let avg_time = run_times.iter().sum::<f64>() / run_times.len() as f64;
-    let avg_throughput = format_throughput(total_size, avg_time);
-
-    println!("\n{}", "-".repeat(65));
-    println!("RESULTS:");
-    println!("  Average duration: {:.4}s", avg_time);
-    println!("  Average throughput: {}", avg_throughput);
-    println!("  Total data: {}", format_size(total_size * iterations));
-    println!("{}", "-".repeat(65));
-
-    // Extract numeric throughput for comparison
-    let throughput_value = (total_size as f64) / avg_time / 1e9;
+    println!("{}", "-".repeat(84));
 
     println!("\nANALYSIS:");
-    if throughput_value >= 80.0 {
+    if best >= TARGET_GBPS {
         println!(
-            "  ✅ EXCELLENT: {} exceeds 80 GB/s storage target",
-            avg_throughput
+            "  ✅ EXCELLENT: {:.2} GB/s exceeds {} GB/s storage target",
+            best, TARGET_GBPS
         );
         println!("     Generation will NOT bottleneck storage");
-    } else if throughput_value >= 50.0 {
+    } else if best >= 50.0 {
         println!(
-            "  ⚠️  GOOD: {} is decent but below 80 GB/s target",
-            avg_throughput
+            "  ⚠️  GOOD: {:.2} GB/s is decent but below {} GB/s target",
+            best, TARGET_GBPS
         );
         println!("     May bottleneck very fast storage");
     } else {
         println!(
-            "  ❌ SLOW: {} is significantly below 80 GB/s",
-            avg_throughput
+            "  ❌ SLOW: {:.2} GB/s is significantly below {} GB/s",
+            best, TARGET_GBPS
         );
         println!("     Will bottleneck storage - needs investigation");
     }
@@ -135,11 +135,6 @@ fn main() {
     println!("\nNOTES:");
     println!("  - This is NATIVE RUST (no Python overhead)");
     println!("  - Buffer is REUSED (zero-copy across chunks)");
-    println!("  - Parallel generation across all cores");
     println!("  - In real workload, add write time: total_time = gen_time + write_time");
-    println!("\nNEXT STEPS:");
-    println!("  1. If throughput > 80 GB/s: Generation is NOT the bottleneck");
-    println!("  2. Test with actual storage writes (O_DIRECT + io_uring)");
-    println!("  3. Compare: storage_write_speed vs generation_speed");
     println!("=================================================================\n");
 }
@@ -3,7 +3,6 @@
 
 use dgen_data::{DataGenerator, GeneratorConfig, NumaMode};
 use std::fs::File;
-use std::io::Write;
 use std::time::Instant;
 
 fn main() -> std::io::Result<()> {
@@ -19,27 +18,16 @@ fn main() -> std::io::Result<()> {
         numa_mode: NumaMode::Auto,
         max_threads: None,
         numa_node: None,
+        ..Default::default()
     };
 
     let mut gen = DataGenerator::new(config);
-    let mut buffer = vec![0u8; chunk_size];
 
     // Open file for writing
     let mut file = File::create("test_output.bin")?;
 
     let start = Instant::now();
-    let mut total_written = 0;
-
-    while !gen.is_complete() {
-        let nbytes = gen.fill_chunk(&mut buffer);
-        if nbytes == 0 {
-            break;
-        }
-
-        // Write to file
-        file.write_all(&buffer[..nbytes])?;
-        total_written += nbytes;
-    }
+    let total_written = gen.write_all_to(&mut file, chunk_size)?;
 
     file.sync_all()?;
     let elapsed = start.elapsed().as_secs_f64();
@@ -22,15 +22,28 @@ try:
         BytesView,
         generate_buffer,
         generate_into_buffer,
+        generate_into_buffer_at,
+        verify_buffer,
+        random_bytes,
+        estimated_peak_bytes,
+        keyed_seed,
+        fits_in_memory,
+        max_safe_oneshot_size,
         Generator,
+        RingGenerator,
+        RingBuffer,
         create_bytearrays,
+        create_numa_buffers,
+        create_and_generate,
+        fill_buffers,
+        generate_to_file,
+        benchmark,
+        DATA_FORMAT_VERSION,
+        DgenRatioTruncationWarning,
+        DgenChunkSizeWarning,
+        DgenParallelismWarning,
+        get_numa_info,  # Always present; reports a single UMA node without NUMA support
     )
-    
-    # Try to import NUMA info (may not be available on all platforms)
-    try:
-        from ._dgen_rs import get_numa_info
-    except ImportError:
-        get_numa_info = None
         
 except ImportError as e:
     raise ImportError(
@@ -46,9 +59,28 @@ __all__ = [
     "generate_buffer",
     "generate_data",
     "generate_into_buffer",
+    "generate_into_buffer_at",
+    "verify_buffer",
+    "random_bytes",
+    "estimated_peak_bytes",
+    "keyed_seed",
+    "fits_in_memory",
+    "max_safe_oneshot_size",
     "fill_buffer",
+    "generate_ndarray",
     "Generator",
+    "RingGenerator",
+    "RingBuffer",
     "create_bytearrays",
+    "create_numa_buffers",
+    "create_and_generate",
+    "fill_buffers",
+    "generate_to_file",
+    "benchmark",
+    "DATA_FORMAT_VERSION",
+    "DgenRatioTruncationWarning",
+    "DgenChunkSizeWarning",
+    "DgenParallelismWarning",
     "get_numa_info",
     "get_system_info",
 ]
@@ -60,6 +92,8 @@ def generate_data(
     compress_ratio: float = 1.0,
     numa_mode: str = "auto",
     max_threads: Optional[int] = None,
+    writable: bool = False,
+    content: str = "random",
 ):
     """
     Generate random data with ZERO-COPY access via buffer protocol.
@@ -73,6 +107,9 @@ def generate_data(
         compress_ratio: Compression ratio (1.0 = incompressible, 3.0 = 3:1 ratio)
         numa_mode: NUMA optimization - \"auto\" (default), \"force\", or \"disabled\"
         max_threads: Maximum threads to use (None = use all cores)
+        writable: Export a writable buffer so memoryview() can modify data in place
+        content: Data layout - "random" (default), "csv[:COLUMNS:FIELD_LEN]", "dict[:NUM_VALUES:VALUE_LEN]" or "pseudo[:PERIOD|TARGET]"
+            (TARGET tunes the period for a compressor: "deflate", "zstd", "lz4" or "generic")
     
     Returns:
         BytesView: Zero-copy buffer (use memoryview() or numpy.frombuffer() for access)
@@ -98,7 +135,65 @@ def generate_data(
         >>> # If you need actual bytes object, call bytes()
         >>> data_bytes = bytes(data)  # This copies, but gives you bytes object
     """
-    return generate_buffer(size, dedup_ratio, compress_ratio, numa_mode, max_threads)
+    return generate_buffer(
+        size,
+        dedup_ratio,
+        compress_ratio,
+        numa_mode,
+        max_threads,
+        writable=writable,
+        content=content,
+    )
+
+
+def generate_ndarray(
+    shape,
+    dtype="float32",
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    seed: Optional[int] = None,
+    max_threads: Optional[int] = None,
+    writable: bool = False,
+):
+    """
+    Generate a NumPy array of random values (zero-copy).
+
+    Generates exactly enough bytes for the array and wraps them with
+    numpy.frombuffer(), so the array shares the generated memory. Values are
+    arbitrary bit patterns: floats may be NaN or infinite, which is fine for
+    throughput and bandwidth tests.
+
+    Args:
+        shape: Array shape (int or tuple of ints)
+        dtype: NumPy dtype (default "float32")
+        dedup_ratio: Deduplication ratio (1.0 = no dedup)
+        compress_ratio: Compression ratio (1.0 = incompressible)
+        seed: Seed for reproducible data (None = fresh entropy per call)
+        max_threads: Maximum threads to use (None = use all cores)
+        writable: Return a writable array (read-only by default)
+
+    Returns:
+        numpy.ndarray: Array of `shape` and `dtype` backed by the generated data
+
+    Example:
+        >>> import dgen_py
+        >>> batch = dgen_py.generate_ndarray((64, 3, 224, 224), dtype="float32", seed=1)
+        >>> batch.nbytes
+        38535168
+    """
+    import numpy as np
+
+    dtype = np.dtype(dtype)
+    count = int(np.prod(shape, dtype=np.int64))
+    data = generate_buffer(
+        count * dtype.itemsize,
+        dedup_ratio,
+        compress_ratio,
+        max_threads=max_threads,
+        writable=writable,
+        seed=seed,
+    )
+    return np.frombuffer(data, dtype=dtype, count=count).reshape(shape)
 
 
 def fill_buffer(
@@ -118,7 +213,7 @@ def fill_buffer(
         buffer: Pre-allocated writable buffer (supports buffer protocol)
         dedup_ratio: Deduplication ratio
         compress_ratio: Compression ratio
-        numa_mode: NUMA optimization - "auto" (default), "force", or "disabled"
+        numa_mode: NUMA optimization - "auto" (default), "force", "disabled", or "interleave"
         max_threads: Maximum threads to use (None = use all cores)
     
     Returns:
@@ -151,8 +246,11 @@ def get_system_info() -> Optional[dict]:
             - num_nodes: Number of NUMA nodes
             - physical_cores: Total physical cores
             - logical_cpus: Total logical CPUs
+            - effective_cpus: CPUs this process may use (affinity and cgroup
+              quota applied); the default thread count
             - is_uma: Whether this is a UMA system
             - deployment_type: Description of deployment type
+            - summary: One line with node count, cores and CPUs per node
         None: If NUMA detection is not available on this platform
     
     Example:
@@ -162,9 +260,6 @@ def get_system_info() -> Optional[dict]:
         ...     print(f"Cores: {info['physical_cores']}")
         ...     print(f"Type: {info['deployment_type']}")
     """
-    if get_numa_info is None:
-        return None
-    
     try:
         return get_numa_info()
     except Exception:
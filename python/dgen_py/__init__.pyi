@@ -1,23 +1,131 @@
 """Type stubs for dgen-py"""
 
-from typing import Optional
+import os
+from typing import Optional, Union
+
+DATA_FORMAT_VERSION: int
+
+class DgenRatioTruncationWarning(UserWarning):
+    """Issued when a fractional dedup/compress ratio is truncated to an integer factor"""
+    ...
+
+class DgenChunkSizeWarning(UserWarning):
+    """Issued when a Generator chunk_size is bumped into [block size, total size]"""
+    ...
+
+class DgenParallelismWarning(UserWarning):
+    """Issued when a Generator block_size leaves each chunk fewer blocks than threads"""
+    ...
+
+class BytesView:
+    """Zero-copy view of generated data (supports memoryview)"""
+    
+    @property
+    def writable(self) -> bool:
+        """Whether memoryview() exports a writable buffer"""
+        ...
+    
+    @property
+    def numa_node(self) -> Optional[int]:
+        """NUMA node the data lives on (None for UMA, UMA fallback, or multi-node placement)"""
+        ...
+    
+    def __len__(self) -> int: ...
+    def __bytes__(self) -> bytes: ...
 
 def generate_buffer(
     size: int,
     dedup_ratio: float = 1.0,
-    compress_ratio: float = 1.0
-) -> bytes:
-    """Generate random data with controllable characteristics"""
+    compress_ratio: float = 1.0,
+    numa_mode: str = "auto",
+    max_threads: Optional[int] = None,
+    numa_node: Optional[Union[int, list[int]]] = None,
+    writable: bool = False,
+    content: str = "random",
+    seed: Optional[int] = None,
+    alignment: Optional[int] = None,
+    cpu_affinity: Optional[list[int]] = None,
+    compressible_fraction: Optional[float] = None,
+    unique_prefix_bytes: int = 0
+) -> BytesView:
+    """Generate random data with controllable characteristics
+
+    With ``alignment`` (e.g. 4096) the buffer address is aligned and the size is
+    rounded up to a multiple of it, so the result can be written with O_DIRECT.
+
+    With ``seed`` set, the bytes match Rust's
+    ``generate_data(GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, Some(seed)))``
+    for the same ``max_threads``.
+    """
     ...
 
 def generate_into_buffer(
     buffer,
     dedup_ratio: float = 1.0,
-    compress_ratio: float = 1.0
+    compress_ratio: float = 1.0,
+    numa_mode: str = "auto",
+    max_threads: Optional[int] = None,
+    numa_node: Optional[Union[int, list[int]]] = None,
+    seed: Optional[int] = None
 ) -> int:
     """Generate data directly into existing buffer (zero-copy)"""
     ...
 
+def generate_into_buffer_at(
+    buffer,
+    logical_offset: int,
+    dataset_size: int,
+    seed: int,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    block_size: Optional[int] = None,
+    content: str = "random"
+) -> int:
+    """Generate bytes [logical_offset, logical_offset+len(buffer)) of a deterministic dataset"""
+    ...
+
+def verify_buffer(
+    buffer,
+    logical_offset: int = 0,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    *,
+    seed: int,
+    dataset_size: Optional[int] = None,
+    block_size: Optional[int] = None,
+    content: str = "random"
+) -> tuple[bool, Optional[int]]:
+    """Compare a read-back buffer against the regenerated dataset: (ok, first_mismatch_offset)"""
+    ...
+
+def random_bytes(size: int, seed: Optional[int] = None) -> BytesView:
+    """Raw Xoshiro256++ keystream bytes, matching Rust's ``KeyStream::new(seed).fill()``"""
+    ...
+
+def estimated_peak_bytes(
+    size: int,
+    max_threads: Optional[int] = None,
+    alignment: Optional[int] = None
+) -> int:
+    """Bytes generate_buffer() allocates for the same arguments (whole blocks, at least one)"""
+    ...
+
+def keyed_seed(dataset_id: int, shard_id: int) -> int:
+    """Seed for shard shard_id of dataset dataset_id (fixed mixing function, stable across versions)"""
+    ...
+
+def fits_in_memory(
+    size: int,
+    max_threads: Optional[int] = None,
+    alignment: Optional[int] = None
+) -> bool:
+    """Whether generate_buffer() with these arguments fits in available memory (True if unknown)"""
+    ...
+
+def max_safe_oneshot_size(max_threads: Optional[int] = None) -> Optional[int]:
+    """Largest generate_buffer() size that fits in available memory (None if unknown)"""
+    ...
+
 class Generator:
     """Streaming data generator"""
     
@@ -27,7 +135,17 @@ class Generator:
         dedup_ratio: float = 1.0,
         compress_ratio: float = 1.0,
         numa_mode: str = "auto",
-        max_threads: Optional[int] = None
+        max_threads: Optional[int] = None,
+        numa_node: Optional[Union[int, list[int]]] = None,
+        chunk_size: Optional[int] = None,
+        block_size: Optional[int] = None,
+        seed: Optional[int] = None,
+        content: str = "random",
+        cpu_affinity: Optional[list[int]] = None,
+        max_gbps: Optional[float] = None,
+        compressible_fraction: Optional[float] = None,
+        unique_prefix_bytes: int = 0,
+        fit_block_size: bool = False
     ) -> None:
         """Create new generator"""
         ...
@@ -35,14 +153,30 @@ class Generator:
     def fill_chunk(self, buffer) -> int:
         """Fill next chunk into buffer"""
         ...
+
+    def verify_chunk(self, buffer) -> tuple[bool, Optional[int]]:
+        """Check buffer against the next bytes of the stream: (True, None) or (False, first bad offset)"""
+        ...
+
+    def fill_fd(self, fd: int, count: int, offset: Optional[int] = None) -> int:
+        """Write the next ``count`` bytes to file descriptor ``fd`` (pwrite at ``offset`` if given)"""
+        ...
     
     def get_chunk(self, chunk_size: int) -> Optional[bytes]:
         """Get next chunk as bytes"""
         ...
     
+    def read_at(self, offset: int, length: int) -> BytesView:
+        """Read an arbitrary range of the stream without moving the position"""
+        ...
+    
     def reset(self) -> None:
         """Reset to start"""
         ...
+
+    def reset_with_new_seed(self) -> int:
+        """Rewind and switch to fresh entropy; returns the new seed"""
+        ...
     
     def position(self) -> int:
         """Get current position"""
@@ -55,6 +189,145 @@ class Generator:
     def is_complete(self) -> bool:
         """Check if complete"""
         ...
+    
+    def close(self) -> None:
+        """Release worker threads and scratch buffers (rebuilt if used again)"""
+        ...
+    
+    def stats(self) -> dict:
+        """Bytes/blocks generated, requested vs realized dedup/compress ratios,
+        page-level dedup estimate, effective seed and gbps (once data is generated)"""
+        ...
+
+    @property
+    def effective_config(self) -> dict:
+        """Settings in use after clamping: total_size, block_size, nblocks,
+        unique_blocks, max_threads, dedup_factor, compress_factor and seed"""
+        ...
+
+class RingBuffer:
+    """One filled chunk of a RingGenerator (read-only, supports memoryview)"""
+
+    @property
+    def offset(self) -> int:
+        """Stream position of the first byte"""
+        ...
+
+    @property
+    def released(self) -> bool:
+        """Whether the slot has been returned to the ring"""
+        ...
+
+    def release(self) -> None:
+        """Return the slot for refilling (BufferError while a memoryview is open)"""
+        ...
+
+    def __enter__(self) -> "RingBuffer": ...
+    def __exit__(self, exc_type, exc_value, traceback) -> bool: ...
+    def __len__(self) -> int: ...
+    def __bytes__(self) -> bytes: ...
+
+class RingGenerator:
+    """Bounded producer filling a fixed set of reusable buffers in rotation"""
+
+    def __init__(
+        self,
+        size: int,
+        slots: int = 4,
+        dedup_ratio: float = 1.0,
+        compress_ratio: float = 1.0,
+        max_threads: Optional[int] = None,
+        chunk_size: Optional[int] = None,
+        block_size: Optional[int] = None,
+        seed: Optional[int] = None,
+        content: str = "random",
+        max_gbps: Optional[float] = None,
+        compressible_fraction: Optional[float] = None,
+        unique_prefix_bytes: int = 0
+    ) -> None:
+        """Create a ring of ``slots`` buffers of ``chunk_size`` bytes"""
+        ...
+
+    def __iter__(self) -> "RingGenerator": ...
+
+    def __next__(self) -> RingBuffer:
+        """Fill the next buffer, blocking until a slot is released"""
+        ...
+
+    @property
+    def slots(self) -> int: ...
+
+    @property
+    def chunk_size(self) -> int: ...
+
+    def free_slots(self) -> int:
+        """Buffers not held by the consumer"""
+        ...
+
+    def position(self) -> int: ...
+    def total_size(self) -> int: ...
+    def is_complete(self) -> bool: ...
+
+def create_bytearrays(count: int, size: int) -> list[bytearray]:
+    """Pre-allocate multiple bytearrays from Rust"""
+    ...
+
+def create_numa_buffers(count: int, size: int, node: int) -> list[BytesView]:
+    """Pre-allocate multiple zeroed writable buffers bound to a NUMA node (requires NUMA support)"""
+    ...
+
+def create_and_generate(
+    count: int,
+    size: int,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    seed: Optional[int] = None,
+    max_threads: Optional[int] = None,
+    block_size: Optional[int] = None
+) -> list[BytesView]:
+    """Allocate and fill multiple buffers in one call (zero-copy views)"""
+    ...
+
+def fill_buffers(
+    buffers: list,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    seed: Optional[int] = None,
+    max_threads: Optional[int] = None,
+    block_size: Optional[int] = None
+) -> int:
+    """Fill a list of pre-allocated buffers in parallel (single call)"""
+    ...
+
+def generate_to_file(
+    path: Union[str, os.PathLike],
+    size: int,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    seed: Optional[int] = None,
+    chunk_size: Optional[int] = None,
+    max_threads: Optional[int] = None,
+    content: str = "random"
+) -> tuple[int, float]:
+    """Write `size` generated bytes to `path` in Rust; returns (bytes_written, elapsed_seconds)"""
+    ...
+
+def benchmark(
+    size: int,
+    dedup_ratio: float = 1.0,
+    compress_ratio: float = 1.0,
+    iterations: int = 5,
+    max_threads: Optional[int] = None,
+    block_size: Optional[int] = None,
+    warmup: int = 1,
+    numa_mode: str = "auto",
+    seed: Optional[int] = None,
+    content: str = "random",
+    compressible_fraction: Optional[float] = None,
+    unique_prefix_bytes: int = 0
+) -> dict:
+    """Measure generation throughput after `warmup` untimed runs (min/avg/max and per-core GB/s)"""
+    ...
 
 def get_numa_info() -> dict:
     """Get NUMA topology information"""
@@ -1,5 +1,7 @@
 """Tests for dgen-py Python bindings"""
 
+import warnings
+
 import pytest
 import dgen_py
 
@@ -107,6 +109,441 @@ def test_generator_reset():
     assert not gen.is_complete()
 
 
+def test_generator_reset_with_new_seed():
+    """Test rewinding with fresh entropy"""
+    size = 1024 * 1024
+    gen = dgen_py.Generator(size=size, seed=1)
+    first = bytearray(size)
+    gen.fill_chunk(first)
+
+    seed = gen.reset_with_new_seed()
+    assert gen.position() == 0
+    second = bytearray(size)
+    gen.fill_chunk(second)
+    assert first != second
+
+    replay = bytearray(size)
+    dgen_py.Generator(size=size, seed=seed).fill_chunk(replay)
+    assert replay == second
+
+
+def test_generator_read_at():
+    """Test random-access reads match the sequential stream"""
+    size = 3 * 1024 * 1024 + 100
+    gen = dgen_py.Generator(size=size, compress_ratio=2.0, seed=42, block_size=1024 * 1024)
+    stream = bytearray(size)
+    gen.fill_chunk(stream)
+
+    gen = dgen_py.Generator(size=size, compress_ratio=2.0, seed=42, block_size=1024 * 1024)
+    view = gen.read_at(1_000_000, 2_000_000)
+    assert bytes(view) == bytes(stream[1_000_000:3_000_000])
+    assert gen.position() == 0
+
+    # Reads past the end are truncated
+    assert len(gen.read_at(size - 10, 100)) == 10
+    assert len(gen.read_at(size + 1, 100)) == 0
+
+
+def test_generator_fill_fd(tmp_path):
+    """Test fill_fd writes the same bytes as fill_chunk, via write and pwrite"""
+    import os
+
+    size = 3 * 1024 * 1024 + 100
+    expected = bytearray(size)
+    dgen_py.Generator(size=size, compress_ratio=2, seed=5, chunk_size=1024 * 1024).fill_chunk(expected)
+
+    path = tmp_path / "data.bin"
+    fd = os.open(path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC)
+    try:
+        gen = dgen_py.Generator(size=size, compress_ratio=2, seed=5, chunk_size=1024 * 1024)
+        assert gen.fill_fd(fd, 2_000_000) == 2_000_000
+        # Past the end of the stream only the remainder is written
+        assert gen.fill_fd(fd, size) == size - 2_000_000
+        assert gen.is_complete()
+        assert gen.fill_fd(fd, 10) == 0
+
+        # Positional writes leave the file position alone
+        gen.reset()
+        assert gen.fill_fd(fd, 4096, offset=size) == 4096
+        assert os.lseek(fd, 0, os.SEEK_CUR) == size
+    finally:
+        os.close(fd)
+
+    data = path.read_bytes()
+    assert data[:size] == expected
+    assert data[size:] == expected[:4096]
+
+    with pytest.raises(OSError):
+        dgen_py.Generator(size=1024).fill_fd(fd, 1024)
+
+
+def test_generate_into_buffer_at():
+    """Test slices filled at logical offsets assemble the same dataset"""
+    size = 3 * 1024 * 1024 + 100
+    whole = bytearray(size)
+    assert dgen_py.generate_into_buffer_at(whole, 0, size, seed=7, compress_ratio=2) == size
+
+    pieces = bytearray(size)
+    view = memoryview(pieces)
+    split = 1_234_567
+    dgen_py.generate_into_buffer_at(view[split:], split, size, seed=7, compress_ratio=2)
+    dgen_py.generate_into_buffer_at(view[:split], 0, size, seed=7, compress_ratio=2)
+    assert pieces == whole
+
+    # Matches the streaming generator for the same dataset
+    mib = 1024 * 1024
+    dgen_py.generate_into_buffer_at(whole, 0, size, seed=7, compress_ratio=2, block_size=mib)
+    stream = bytearray(size)
+    dgen_py.Generator(size=size, compress_ratio=2, seed=7, block_size=mib).fill_chunk(stream)
+    assert stream == whole
+
+
+def test_verify_buffer():
+    """Test verify_buffer detects corruption at the right offset"""
+    size = 2 * 1024 * 1024
+    data = bytearray(size)
+    dgen_py.generate_into_buffer_at(data, 0, size, seed=11, dedup_ratio=2)
+    assert dgen_py.verify_buffer(data, dedup_ratio=2, seed=11) == (True, None)
+
+    # A slice verifies at its logical offset within the full dataset
+    part = bytes(data[4096:8192])
+    assert dgen_py.verify_buffer(part, 4096, dedup_ratio=2, seed=11, dataset_size=size)[0]
+
+    data[123_456] ^= 0xFF
+    assert dgen_py.verify_buffer(data, dedup_ratio=2, seed=11) == (False, 123_456)
+    assert not dgen_py.verify_buffer(bytes(size), dedup_ratio=2, seed=12)[0]
+
+
+def test_ratio_truncation_warning():
+    """Test truncation warnings use a filterable category, once per call site"""
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("default")
+        for _ in range(10):
+            dgen_py.generate_buffer(1024, compress_ratio=2.5)
+    assert len(caught) == 1
+    assert issubclass(caught[0].category, dgen_py.DgenRatioTruncationWarning)
+    assert caught[0].filename == __file__
+
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        warnings.simplefilter("ignore", dgen_py.DgenRatioTruncationWarning)
+        dgen_py.generate_buffer(1024, dedup_ratio=1.5)
+    assert caught == []
+
+
+def test_numa_binding_args():
+    """Test numa_mode='interleave' and list-valued numa_node are accepted"""
+    size = 1024 * 1024
+    assert len(dgen_py.generate_buffer(size, numa_mode="interleave")) == size
+    assert len(dgen_py.generate_buffer(size, numa_node=[0])) == size
+    assert dgen_py.generate_into_buffer(bytearray(size), numa_node=0) == size
+
+    # UMA buffers (and UMA fallbacks) report no node
+    assert dgen_py.generate_buffer(size).numa_node is None
+    view = dgen_py.generate_buffer(size, numa_node=0)
+    assert view.numa_node in (None, 0)
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_buffer(size, numa_mode="interleave", numa_node=[0])
+    with pytest.raises(ValueError):
+        dgen_py.generate_buffer(size, numa_mode="striped")
+
+
+def test_generator_close():
+    """close() releases the pool; the generator stays usable"""
+    gen = dgen_py.Generator(size=16 * 1024 * 1024, max_threads=2, seed=3)
+    buf = bytearray(8 * 1024 * 1024)
+    while not gen.is_complete():
+        gen.fill_chunk(buf)
+    gen.close()
+    gen.close()
+
+    gen.reset()
+    again = bytearray(len(buf))
+    gen.fill_chunk(again)
+    fresh = bytearray(len(buf))
+    dgen_py.Generator(size=16 * 1024 * 1024, max_threads=2, seed=3).fill_chunk(fresh)
+    assert again == fresh
+
+def test_generator_chunk_size_bounds():
+    """Out-of-range chunk sizes are bumped into [block size, total size] with a warning"""
+    import warnings
+
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        small = dgen_py.Generator(size=8 * 1024 * 1024, chunk_size=1024)
+        large = dgen_py.Generator(size=2 * 1024 * 1024, chunk_size=64 * 1024 * 1024)
+        tiny = dgen_py.Generator(size=1000, chunk_size=512)
+        ok = dgen_py.Generator(size=8 * 1024 * 1024, chunk_size=2 * 1024 * 1024)
+    assert small.chunk_size == 1024 * 1024
+    assert large.chunk_size == 2 * 1024 * 1024
+    assert tiny.chunk_size == tiny.total_size()
+    assert ok.chunk_size == 2 * 1024 * 1024
+    assert len(caught) == 3
+    assert all(issubclass(w.category, dgen_py.DgenChunkSizeWarning) for w in caught)
+
+def test_generator_parallelism_warning():
+    """Chunks of too few blocks for the threads warn, or shrink the blocks on request"""
+    import warnings
+
+    mib = 1024 * 1024
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        trap = dgen_py.Generator(size=256 * mib, max_threads=8, chunk_size=32 * mib,
+                                 block_size=32 * mib, seed=5)
+        dgen_py.Generator(size=256 * mib, max_threads=8, chunk_size=32 * mib, seed=5)
+        dgen_py.Generator(size=256 * mib, max_threads=8, chunk_size=256 * mib,
+                          block_size=32 * mib, seed=5)
+    assert len(caught) == 1
+    assert issubclass(caught[0].category, dgen_py.DgenParallelismWarning)
+    assert "fit_block_size=True" in str(caught[0].message)
+
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        fitted = dgen_py.Generator(size=256 * mib, max_threads=8, chunk_size=32 * mib,
+                                   block_size=32 * mib, seed=5, fit_block_size=True)
+    explicit = dgen_py.Generator(size=256 * mib, max_threads=8, chunk_size=32 * mib,
+                                 block_size=4 * mib, seed=5)
+    a, b, c = bytearray(32 * mib), bytearray(32 * mib), bytearray(32 * mib)
+    fitted.fill_chunk(a)
+    explicit.fill_chunk(b)
+    trap.fill_chunk(c)
+    assert a == b
+    assert a != c
+
+
+def test_cpu_affinity():
+    """Test pinning to an explicit CPU list leaves the data unchanged"""
+    size = 4 * 1024 * 1024
+    expected = bytes(dgen_py.generate_buffer(size, seed=9, max_threads=1))
+    pinned = dgen_py.generate_buffer(size, seed=9, max_threads=1, cpu_affinity=[0])
+    assert bytes(pinned) == expected
+
+    gen = dgen_py.Generator(size, seed=9, max_threads=1, cpu_affinity=[0, 0])
+    buf = bytearray(size)
+    assert gen.fill_chunk(buf) == size
+    assert bytes(buf) == expected
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_buffer(size, cpu_affinity=[])
+
+
+def test_generator_verify_chunk():
+    """Test streaming verification against the generator's own output"""
+    size = 3 * 1024 * 1024 + 5
+    data = bytearray(size)
+    dgen_py.Generator(size=size, compress_ratio=2, seed=8, block_size=1024 * 1024).fill_chunk(data)
+
+    verifier = dgen_py.Generator(size=size, compress_ratio=2, seed=8, block_size=1024 * 1024)
+    chunk = 700 * 1024
+    for start in range(0, size, chunk):
+        assert verifier.verify_chunk(data[start:start + chunk]) == (True, None)
+    assert verifier.is_complete()
+
+    data[2_000_000] ^= 0xFF
+    verifier.reset()
+    assert verifier.verify_chunk(data) == (False, 2_000_000)
+    data[2_000_000] ^= 0xFF
+    verifier.reset()
+    assert verifier.verify_chunk(bytes(data) + b"extra") == (False, size)
+
+
+def test_compressible_fraction():
+    """Test setting the compressible share directly instead of a ratio"""
+    size = 4 * 1024 * 1024
+    data = bytes(dgen_py.generate_buffer(size, compressible_fraction=0.7, seed=5, max_threads=2))
+    zeros = data.count(0)
+    assert abs(zeros / size - 0.7) < 0.01
+
+    # The fraction overrides compress_ratio; (N-1)/N matches ratio N
+    quarter = dgen_py.generate_buffer(size, compress_ratio=4, seed=5, max_threads=2)
+    frac = dgen_py.generate_buffer(size, compress_ratio=2, compressible_fraction=0.75,
+                                   seed=5, max_threads=2)
+    assert bytes(quarter) == bytes(frac)
+
+    gen = dgen_py.Generator(size=size, compressible_fraction=0.7, seed=5, max_threads=2)
+    buf = bytearray(size)
+    gen.fill_chunk(buf)
+    assert abs(buf.count(0) / size - 0.7) < 0.01
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_buffer(size, compressible_fraction=1.5)
+
+
+def test_unique_prefix_bytes():
+    """Test per-block unique prefixes on top of the dedup layout"""
+    block = 1024 * 1024
+    size = 4 * block
+    gen = dgen_py.Generator(size=size, dedup_ratio=4, seed=6, unique_prefix_bytes=512)
+    data = bytearray(size)
+    gen.fill_chunk(data)
+    blocks = [bytes(data[i:i + block]) for i in range(0, size, block)]
+    # All four blocks are copies of one unique block except for the prefix
+    assert len({b[:512] for b in blocks}) == 4
+    assert len({b[512:] for b in blocks}) == 1
+    assert gen.stats()["realized_dedup"] == 1.0
+
+
+def test_generator_max_gbps():
+    """Test Generator pacing with max_gbps"""
+    import time
+
+    size = 4 * 1024 * 1024
+    # 20 MB/s with a 2 MB burst: the remaining ~2.2 MB take at least ~100 ms
+    gen = dgen_py.Generator(size=size, seed=3, block_size=1024 * 1024, max_gbps=0.02)
+    buffer = bytearray(1024 * 1024)
+    start = time.monotonic()
+    paced = bytearray()
+    while not gen.is_complete():
+        n = gen.fill_chunk(buffer)
+        paced += buffer[:n]
+    assert time.monotonic() - start >= 0.09
+
+    unpaced = bytearray(size)
+    dgen_py.Generator(size=size, seed=3, block_size=1024 * 1024).fill_chunk(unpaced)
+    assert paced == unpaced
+
+    with pytest.raises(ValueError):
+        dgen_py.Generator(size=size, max_gbps=0)
+
+
+def test_ring_generator():
+    """Test RingGenerator slot reuse and output"""
+    import threading
+
+    size = 5 * 1024 * 1024 + 321
+    chunk = 2 * 1024 * 1024
+    ring = dgen_py.RingGenerator(size=size, slots=2, chunk_size=chunk, seed=9, compress_ratio=2)
+    assert (ring.slots, ring.chunk_size, ring.free_slots()) == (2, chunk, 2)
+
+    first = next(ring)
+    second = next(ring)
+    assert (first.offset, second.offset) == (0, chunk)
+    assert ring.free_slots() == 0
+
+    # A released slot unblocks the producer
+    view = memoryview(first)
+    with pytest.raises(BufferError):
+        first.release()
+    output = bytearray(view)
+    view.release()
+    threading.Timer(0.05, first.release).start()
+    third = next(ring)
+    assert first.released and len(first) == 0
+    with second:
+        output += memoryview(second)
+    assert second.released
+    output += bytes(third)
+    del third
+    assert ring.is_complete() and list(ring) == []
+    assert ring.free_slots() == 2
+
+    expected = bytearray(size)
+    dgen_py.Generator(size=size, seed=9, compress_ratio=2).fill_chunk(expected)
+    assert output == expected
+
+    with pytest.raises(ValueError):
+        dgen_py.RingGenerator(size=size, slots=0)
+
+
+def test_generator_stats():
+    """Test Generator.stats() reports progress and realized ratios"""
+    size = 8 * 1024 * 1024
+    gen = dgen_py.Generator(size=size, dedup_ratio=2, compress_ratio=2, block_size=1024 * 1024)
+    stats = gen.stats()
+    assert stats["bytes_generated"] == 0
+    assert "gbps" not in stats
+
+    buffer = bytearray(1024 * 1024)
+    while not gen.is_complete():
+        gen.fill_chunk(buffer)
+
+    stats = gen.stats()
+    assert stats["bytes_generated"] == size
+    assert stats["blocks_generated"] == 8
+    assert stats["cache_hits"] == 0
+    assert 0 <= stats["threads_pinned"] <= stats["threads_total"]
+    assert stats["realized_dedup"] == 2.0
+    assert stats["realized_compress"] == 2.0
+    assert stats["requested_dedup"] == 2
+    # Zero pages alias across unique blocks, so page-level dedup exceeds block-level
+    assert stats["page_dedup_estimate"] > stats["realized_dedup"]
+    assert stats["gbps"] > 0
+    assert stats["numa_compiled"] or not stats["numa_active"]
+
+    # An unseeded stream reports the entropy it used, which replays it
+    first = bytearray(size)
+    unseeded = dgen_py.Generator(size=size, compress_ratio=2, block_size=1024 * 1024)
+    unseeded.fill_chunk(first)
+    replay = dgen_py.Generator(size=size, compress_ratio=2, block_size=1024 * 1024,
+                               seed=unseeded.stats()["effective_seed"])
+    second = bytearray(size)
+    replay.fill_chunk(second)
+    assert first == second
+
+
+def test_effective_config():
+    """Test Generator.effective_config reports clamped settings and the seed"""
+    gen = dgen_py.Generator(size=100, dedup_ratio=2, block_size=64 * 1024, max_threads=1)
+    config = gen.effective_config
+    assert config["block_size"] == 256 * 1024
+    assert config["total_size"] == gen.total_size()
+    assert config["nblocks"] == 1
+    assert config["unique_blocks"] == 1
+    assert config["max_threads"] == 1
+    assert config["seed"] == gen.stats()["effective_seed"]
+
+
+def test_csv_content():
+    """Test CSV content mode produces well-formed rows"""
+    # Seeded, so the data is one 1 MiB block on any machine
+    data = bytes(dgen_py.generate_buffer(1024 * 1024, content="csv:4:8", seed=3))
+    rows = data.split(b"\n")
+    # Rows restart at every block, so the block's last row is cut short
+    assert rows[-1] == b""
+    for row in rows[:-2]:
+        fields = row.split(b",")
+        assert len(fields) == 4
+        assert all(len(f) == 8 for f in fields)
+
+    gen = dgen_py.Generator(size=1024 * 1024, seed=1, content="csv:4:8")
+    chunk = gen.get_chunk(100)
+    assert bytes(chunk).count(b",") > 0
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_data(1024, content="xml")
+
+
+def test_dictionary_content():
+    """Test dictionary content draws every slot from a small value set"""
+    data = bytes(dgen_py.generate_data(1024 * 1024, content="dict:8:32"))
+    values = {data[i:i + 32] for i in range(0, len(data) - 31, 32)}
+    assert 1 < len(values) <= 8
+
+
+def test_pseudo_compressible_content():
+    """Test pseudo-compressible regions only compress within the compressor's window"""
+    import zlib
+
+    def zlib_ratio(content):
+        data = bytes(dgen_py.generate_buffer(4 * 1024 * 1024, compress_ratio=4.0,
+                                             content=content, seed=5))
+        assert data.count(0) < len(data) // 64
+        return len(data) / len(zlib.compress(data, 1))
+
+    # A 16 KiB period repeats inside DEFLATE's 32 KiB window, a 64 KiB one does not
+    assert zlib_ratio("pseudo:16384") > 2.0
+    assert zlib_ratio("pseudo") < 1.1
+    # Compressor targets pick the period: deflate within zlib's window, zstd beyond it
+    assert zlib_ratio("pseudo:deflate") > 2.0
+    assert zlib_ratio("pseudo:zstd") < 1.1
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_data(1024, content="pseudo:0")
+    with pytest.raises(ValueError):
+        dgen_py.generate_data(1024, content="pseudo:brotli")
+
+
 def test_numpy_integration():
     """Test NumPy array integration (if numpy available)"""
     try:
@@ -123,6 +560,233 @@ def test_numpy_integration():
     assert arr.sum() > 0  # Should have non-zero data
 
 
+def test_generate_ndarray():
+    """Test zero-copy typed NumPy arrays (if numpy available)"""
+    try:
+        import numpy as np
+    except ImportError:
+        pytest.skip("NumPy not available")
+
+    arr = dgen_py.generate_ndarray((4, 256, 256), dtype="float32", seed=3)
+    assert arr.shape == (4, 256, 256)
+    assert arr.dtype == np.float32
+    assert not arr.flags.writeable
+    raw = dgen_py.generate_buffer(arr.nbytes, seed=3)
+    assert arr.tobytes() == bytes(raw)
+
+    ints = dgen_py.generate_ndarray(1000, dtype=np.uint32, writable=True)
+    assert ints.shape == (1000,)
+    ints[0] = 7
+    assert ints[0] == 7
+
+
+def test_writable_bytes_view():
+    """Test opt-in writable buffer protocol on BytesView"""
+    data = dgen_py.generate_buffer(1024 * 1024, writable=True)
+    assert data.writable
+
+    view = memoryview(data)
+    assert not view.readonly
+    view[0] = 0xAB
+    assert memoryview(data)[0] == 0xAB
+
+    # Default views stay read-only
+    ro = dgen_py.generate_buffer(1024 * 1024)
+    assert not ro.writable
+    assert memoryview(ro).readonly
+
+
+def test_create_and_generate():
+    """Test combined allocate-and-fill of many buffers"""
+    objs = dgen_py.create_and_generate(16, 64 * 1024, compress_ratio=2, seed=42)
+    again = dgen_py.create_and_generate(16, 64 * 1024, compress_ratio=2, seed=42)
+
+    assert len(objs) == 16
+    assert all(len(o) == 64 * 1024 for o in objs)
+    assert bytes(objs[0]) == bytes(again[0])
+    assert bytes(objs[0]) != bytes(objs[1])
+
+
+def test_create_numa_buffers():
+    """Test NUMA-bound buffer allocation (if NUMA support is compiled in)"""
+    try:
+        chunks = dgen_py.create_numa_buffers(count=4, size=1024 * 1024, node=0)
+    except RuntimeError:
+        pytest.skip("NUMA support not available")
+
+    assert len(chunks) == 4
+    gen = dgen_py.Generator(size=4 * 1024 * 1024, seed=5, block_size=1024 * 1024)
+    for buf in chunks:
+        assert len(buf) == 1024 * 1024
+        gen.fill_chunk(buf)
+
+    expected = dgen_py.Generator(size=4 * 1024 * 1024, seed=5, block_size=1024 * 1024)
+    out = bytearray(1024 * 1024)
+    for buf in chunks:
+        expected.fill_chunk(out)
+        assert bytes(buf) == bytes(out)
+
+
+def test_create_numa_buffers_start_zeroed():
+    """Fresh NUMA buffers read as zeros, never as leftover process memory"""
+    try:
+        chunks = dgen_py.create_numa_buffers(count=3, size=256 * 1024 + 7, node=0)
+    except RuntimeError:
+        pytest.skip("NUMA support not available")
+
+    for buf in chunks:
+        assert bytes(buf) == bytes(256 * 1024 + 7)
+
+
+def test_fill_buffers():
+    """Test parallel fill of a list of buffers"""
+    chunks = dgen_py.create_bytearrays(count=8, size=256 * 1024)
+    total = dgen_py.fill_buffers(chunks, compress_ratio=2, seed=7)
+    assert total == 8 * 256 * 1024
+
+    again = dgen_py.create_bytearrays(count=8, size=256 * 1024)
+    dgen_py.fill_buffers(again, compress_ratio=2, seed=7)
+    assert chunks == again
+
+    with pytest.raises(ValueError):
+        dgen_py.fill_buffers([bytes(16)])
+
+
+def test_generate_to_file(tmp_path):
+    """Test writing a file from Rust"""
+    path = tmp_path / "data.bin"
+    size = 3 * 1024 * 1024 + 17
+    written, secs = dgen_py.generate_to_file(path, size, compress_ratio=2, seed=9,
+                                             chunk_size=1024 * 1024, max_threads=2)
+    assert written == size
+    assert secs >= 0
+    expected = dgen_py.generate_buffer(size, compress_ratio=2, seed=9, max_threads=2)
+    assert path.read_bytes() == bytes(expected)
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_to_file(path, size, chunk_size=0)
+    with pytest.raises(OSError):
+        dgen_py.generate_to_file(tmp_path / "missing" / "data.bin", size)
+
+
+# (size, dedup_ratio, compress_ratio, seed, max_threads, CRC-32 of the output),
+# computed by the Rust test generator::tests::test_python_api_reference_output.
+# Keep both tables in sync when the data format changes.
+RUST_REFERENCE = [
+    (3 * 1024 * 1024 + 12345, 1.0, 2.0, 42, 3412949712),
+    (8 * 1024 * 1024, 2.0, 3.5, 7, 3967803206),
+]
+
+
+def test_rust_reference_output():
+    """Test seeded generate_buffer output is byte-identical to Rust generate_data"""
+    import zlib
+
+    with warnings.catch_warnings():
+        warnings.simplefilter("ignore", dgen_py.DgenRatioTruncationWarning)
+        for size, dedup, compress, seed, crc in RUST_REFERENCE:
+            data = dgen_py.generate_buffer(size, dedup, compress, seed=seed)
+            assert len(data) == size
+            assert zlib.crc32(memoryview(data)) == crc
+
+            single = dgen_py.generate_buffer(size, dedup, compress, max_threads=1, seed=seed)
+            assert zlib.crc32(memoryview(single)) == crc
+
+            buf = bytearray(size)
+            dgen_py.generate_into_buffer(buf, dedup, compress, seed=seed)
+            assert zlib.crc32(buf) == crc
+
+            # The streaming Generator produces the same bytes
+            gen = dgen_py.Generator(size, dedup, compress, seed=seed)
+            streamed = bytearray(size)
+            assert gen.fill_chunk(streamed) == size
+            assert zlib.crc32(streamed) == crc
+
+
+def test_aligned_generate_buffer():
+    """Test alignment= returns an O_DIRECT-ready buffer with the same bytes"""
+    import ctypes
+
+    size = 1024 * 1024 + 10_000
+    data = dgen_py.generate_buffer(size, alignment=4096, seed=8, writable=True)
+    assert len(data) == 1024 * 1024 + 12_288
+    assert ctypes.addressof(ctypes.c_char.from_buffer(memoryview(data))) % 4096 == 0
+    assert bytes(data) == bytes(dgen_py.generate_buffer(len(data), seed=8))
+
+    with pytest.raises(ValueError):
+        dgen_py.generate_buffer(4096, alignment=0)
+
+
+def test_random_bytes():
+    """Test raw keystream bytes are seedable and incompressible"""
+    import zlib
+
+    data = dgen_py.random_bytes(1024 * 1024, seed=3)
+    assert len(data) == 1024 * 1024
+    assert bytes(data) == bytes(dgen_py.random_bytes(1024 * 1024, seed=3))
+    assert bytes(data) != bytes(dgen_py.random_bytes(1024 * 1024, seed=4))
+    assert len(zlib.compress(bytes(data))) > len(data)
+
+    # A shorter request (a multiple of 8 bytes) is a prefix of the same stream
+    assert bytes(dgen_py.random_bytes(96, seed=3)) == bytes(data)[:96]
+    assert len(dgen_py.random_bytes(0)) == 0
+
+
+def test_estimated_peak_bytes():
+    """Test the one-shot allocation estimate covers whole blocks"""
+    assert dgen_py.estimated_peak_bytes(10000) == 1024 * 1024
+
+    size = 3 * 1024 * 1024 + 1
+    peak = dgen_py.estimated_peak_bytes(size, max_threads=2)
+    assert size < peak < size + 32 * 1024 * 1024
+    assert len(dgen_py.generate_buffer(size, max_threads=2)) <= peak
+
+    with pytest.raises(ValueError):
+        dgen_py.estimated_peak_bytes(size, alignment=0)
+
+
+def test_keyed_seed():
+    """Test shard seeds derived from (dataset_id, shard_id)"""
+    seed = dgen_py.keyed_seed(7, 3)
+    assert seed == dgen_py.keyed_seed(dataset_id=7, shard_id=3)
+    assert seed == 2261712899855144482
+    assert dgen_py.keyed_seed(1, 0) != dgen_py.keyed_seed(0, 1)
+
+    size = 2 * 1024 * 1024
+    a = dgen_py.generate_buffer(size, seed=seed, max_threads=2)
+    b = dgen_py.generate_buffer(size, seed=seed, max_threads=2)
+    assert bytes(a) == bytes(b)
+
+
+def test_fits_in_memory():
+    """Test the one-shot size check against available memory"""
+    assert dgen_py.fits_in_memory(4096)
+    limit = dgen_py.max_safe_oneshot_size(max_threads=2)
+    if limit is None:
+        pytest.skip("available memory unknown on this platform")
+    assert limit >= 1024 * 1024
+    assert dgen_py.estimated_peak_bytes(limit, max_threads=2) == limit
+    assert not dgen_py.fits_in_memory(2**62)
+
+
+def test_benchmark():
+    """Test the Rust-side timing loop with warmup"""
+    size = 2 * 1024 * 1024
+    stats = dgen_py.benchmark(size, iterations=3, warmup=2, max_threads=2,
+                              compress_ratio=2, seed=7)
+    assert (stats['iterations'], stats['warmup'], stats['threads']) == (3, 2, 2)
+    assert stats['bytes_per_iteration'] == size
+    assert 0 < stats['min_gbps'] <= stats['avg_gbps'] <= stats['max_gbps']
+    assert stats['avg_gbps'] == stats['total_gbps']
+    assert abs(stats['per_core_gbps'] - stats['avg_gbps'] / 2) < 1e-9
+
+    stats = dgen_py.benchmark(size, iterations=1, warmup=0, content="csv")
+    assert stats['warmup'] == 0
+
+    with pytest.raises(ValueError):
+        dgen_py.benchmark(size, compressible_fraction=1.5)
+
+
 def test_system_info():
     """Test NUMA system info"""
     info = dgen_py.get_system_info()
@@ -134,10 +798,12 @@ def test_system_info():
         assert 'logical_cpus' in info
         assert 'is_uma' in info
         assert 'deployment_type' in info
+        assert info['summary'].startswith(f"{info['num_nodes']} NUMA node")
         
         assert info['num_nodes'] >= 1
         assert info['physical_cores'] >= 1
         assert info['logical_cpus'] >= info['physical_cores']
+        assert 1 <= info['effective_cpus'] <= info['logical_cpus']
 
 
 if __name__ == '__main__':
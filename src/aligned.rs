@@ -0,0 +1,151 @@
+// src/aligned.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Heap buffers with a caller-chosen address alignment
+//!
+//! `Vec<u8>` only guarantees `align_of::<u8>()`, but `O_DIRECT` writes require the
+//! buffer address to be aligned to the device's logical block size. Large
+//! allocations from the system allocator typically land 16 bytes past a page
+//! boundary, so they fail with `EINVAL` unless copied into an aligned bounce buffer.
+
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// Zero-initialized byte buffer whose start address is a multiple of `align()`
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+}
+
+// SAFETY: AlignedBuffer uniquely owns its allocation, like Vec<u8>
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocate `len` zeroed bytes aligned to `align`
+    ///
+    /// # Panics
+    /// If `align` is not a power of two, or `len` rounded up to `align` overflows
+    /// `isize`. Aborts on allocation failure, like `Vec`.
+    pub fn zeroed(len: usize, align: usize) -> Self {
+        let layout = Layout::from_size_align(len, align)
+            .expect("alignment must be a power of two and the size must fit in isize");
+        let ptr = if len == 0 {
+            // Zero-sized allocations are not allowed; any aligned non-null pointer will do
+            NonNull::new(align as *mut u8).expect("alignment is non-zero")
+        } else {
+            // SAFETY: layout has a non-zero size
+            let raw = unsafe { alloc::alloc_zeroed(layout) };
+            NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+        };
+        Self { ptr, len, layout }
+    }
+
+    /// Number of valid bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer holds no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bytes allocated (the length the buffer was created with)
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Alignment of the start address in bytes
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Set the number of valid bytes, clamped to `capacity()`
+    ///
+    /// Bytes exposed by growing keep whatever was last written there (zeros if
+    /// never written).
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len.min(self.capacity());
+    }
+
+    /// Raw pointer to the first byte
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Mutable raw pointer to the first byte
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: ptr is valid for capacity() >= len initialized bytes
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as for deref(), and &mut self gives exclusive access
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: allocated in zeroed() with this exact layout
+            unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+        }
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("align", &self.align())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_buffer() {
+        for align in [1, 512, 4096, 2 * 1024 * 1024] {
+            let mut buf = AlignedBuffer::zeroed(3 * 4096 + 7, align);
+            assert_eq!(buf.as_ptr() as usize % align, 0);
+            assert_eq!(buf.len(), 3 * 4096 + 7);
+            assert!(buf.iter().all(|&b| b == 0));
+
+            buf[100] = 1;
+            buf.set_len(50);
+            assert_eq!(buf.len(), 50);
+            buf.set_len(usize::MAX);
+            assert_eq!(buf.len(), buf.capacity());
+            assert_eq!(buf[100], 1);
+        }
+
+        let empty = AlignedBuffer::zeroed(0, 4096);
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_ptr() as usize % 4096, 0);
+    }
+}
@@ -0,0 +1,226 @@
+// src/benchmark.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Throughput measurement for data generation
+//!
+//! Replaces the hand-rolled timing loops in the examples with one tested API,
+//! for both one-shot `generate_data()` and streaming `DataGenerator` generation
+
+use std::time::Instant;
+
+use crate::generator::{generate_data, DataGenerator, GeneratorConfig};
+
+/// Throughput statistics from `benchmark()`
+///
+/// All rates are in GB/s (10^9 bytes per second), matching the examples and docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    /// Number of timed iterations (excluding warmup)
+    pub iterations: usize,
+    /// Number of untimed warmup iterations run first
+    pub warmup: usize,
+    /// Bytes generated per iteration
+    pub bytes_per_iteration: usize,
+    /// Thread count used for generation
+    pub threads: usize,
+    /// Mean aggregate throughput across iterations
+    pub total_gbps: f64,
+    /// Mean throughput divided by thread count
+    pub per_core_gbps: f64,
+    /// Slowest iteration
+    pub min_gbps: f64,
+    /// Fastest iteration
+    pub max_gbps: f64,
+    /// Standard deviation of per-iteration throughput
+    pub stddev_gbps: f64,
+}
+
+/// Measure `generate_data()` throughput for a configuration
+///
+/// Runs one untimed warmup iteration (thread spawn, page faults), then `iterations`
+/// timed runs, and returns aggregate and per-core statistics. See
+/// [`benchmark_with_warmup`] to choose the number of warmup iterations.
+///
+/// # Example
+/// ```rust,no_run
+/// use dgen_data::{benchmark, GeneratorConfig};
+///
+/// let config = GeneratorConfig {
+///     size: 1024 * 1024 * 1024,
+///     ..Default::default()
+/// };
+/// let result = benchmark(&config, 5);
+/// println!(
+///     "{:.2} GB/s ({:.2} GB/s per core)",
+///     result.total_gbps, result.per_core_gbps
+/// );
+/// ```
+pub fn benchmark(config: &GeneratorConfig, iterations: usize) -> BenchmarkResult {
+    benchmark_with_warmup(config, iterations, 1)
+}
+
+/// `benchmark()` with `warmup` untimed iterations before the timed ones
+///
+/// The first run in a process pays for thread-pool startup and fresh page
+/// faults; more warmup runs let allocator and CPU frequency settle too. `warmup`
+/// 0 times the cold start as well.
+pub fn benchmark_with_warmup(
+    config: &GeneratorConfig,
+    iterations: usize,
+    warmup: usize,
+) -> BenchmarkResult {
+    measure(
+        config,
+        iterations,
+        warmup,
+        config.generation_threads(),
+        || generate_data(config.clone()).len(),
+    )
+}
+
+/// Measure streaming `DataGenerator::fill_chunk()` throughput for a configuration
+///
+/// Each iteration streams all `config.size` bytes through one reused
+/// `chunk_size`-byte buffer, as a storage writer would, after one untimed warmup
+/// iteration. Generator construction (and its thread pool) is timed too, since
+/// every stream pays for it.
+///
+/// # Example
+/// ```rust,no_run
+/// use dgen_data::{benchmark_streaming, GeneratorConfig};
+///
+/// let config = GeneratorConfig {
+///     size: 10 * 1024 * 1024 * 1024,
+///     ..Default::default()
+/// };
+/// let result = benchmark_streaming(&config, 64 * 1024 * 1024, 3);
+/// println!("{:.2} GB/s", result.total_gbps);
+/// ```
+pub fn benchmark_streaming(
+    config: &GeneratorConfig,
+    chunk_size: usize,
+    iterations: usize,
+) -> BenchmarkResult {
+    let mut buffer = vec![0u8; chunk_size.max(1)];
+    measure(config, iterations, 1, config.pool_threads(), || {
+        let mut generator = DataGenerator::new(config.clone());
+        let mut total = 0;
+        loop {
+            let nbytes = generator.fill_chunk(&mut buffer);
+            if nbytes == 0 {
+                break total;
+            }
+            total += nbytes;
+        }
+    })
+}
+
+/// Run `generate` (returning bytes generated) `warmup` times untimed, then
+/// `iterations` (at least 1) times timed
+fn measure(
+    config: &GeneratorConfig,
+    iterations: usize,
+    warmup: usize,
+    threads: usize,
+    mut generate: impl FnMut() -> usize,
+) -> BenchmarkResult {
+    let iterations = iterations.max(1);
+    let threads = threads.max(1);
+
+    tracing::info!(
+        "Benchmarking: size={}, iterations={}, warmup={}, threads={}",
+        config.size,
+        iterations,
+        warmup,
+        threads
+    );
+
+    // Warmup (not timed)
+    for _ in 0..warmup {
+        generate();
+    }
+
+    let mut bytes_per_iteration = 0;
+    let rates: Vec<f64> = (0..iterations)
+        .map(|i| {
+            let start = Instant::now();
+            bytes_per_iteration = generate();
+            let elapsed = start.elapsed().as_secs_f64();
+            let gbps = bytes_per_iteration as f64 / elapsed / 1e9;
+            tracing::debug!("Iteration {}: {:.2} GB/s", i, gbps);
+            gbps
+        })
+        .collect();
+
+    let mean = rates.iter().sum::<f64>() / rates.len() as f64;
+    let variance = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / rates.len() as f64;
+
+    BenchmarkResult {
+        iterations,
+        warmup,
+        bytes_per_iteration,
+        threads,
+        total_gbps: mean,
+        per_core_gbps: mean / threads as f64,
+        min_gbps: rates.iter().copied().fold(f64::INFINITY, f64::min),
+        max_gbps: rates.iter().copied().fold(0.0, f64::max),
+        stddev_gbps: variance.sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLOCK_SIZE;
+
+    #[test]
+    fn test_benchmark_stats() {
+        let config = GeneratorConfig {
+            size: BLOCK_SIZE * 4,
+            max_threads: Some(2),
+            ..Default::default()
+        };
+        let result = benchmark(&config, 3);
+
+        assert_eq!((result.iterations, result.warmup), (3, 1));
+        assert_eq!(
+            result.threads,
+            if cfg!(feature = "parallel") { 2 } else { 1 }
+        );
+        assert_eq!(result.bytes_per_iteration, BLOCK_SIZE * 4);
+        assert!(result.min_gbps > 0.0);
+        assert!(result.min_gbps <= result.total_gbps && result.total_gbps <= result.max_gbps);
+        assert!((result.per_core_gbps - result.total_gbps / result.threads as f64).abs() < 1e-9);
+
+        // No warmup still reports the generated size; iterations are at least 1
+        let result = benchmark_with_warmup(&config, 0, 0);
+        assert_eq!((result.iterations, result.warmup), (1, 0));
+        assert_eq!(result.bytes_per_iteration, BLOCK_SIZE * 4);
+
+        // Threads are counted the way generate_data() sizes its pool
+        let pinned = GeneratorConfig {
+            cpu_affinity: Some(vec![0]),
+            ..config.clone()
+        };
+        assert_eq!(benchmark_with_warmup(&pinned, 1, 0).threads, 1);
+    }
+
+    #[test]
+    fn test_benchmark_streaming() {
+        let config = GeneratorConfig {
+            size: BLOCK_SIZE * 4 + 100,
+            max_threads: Some(2),
+            ..Default::default()
+        };
+        let result = benchmark_streaming(&config, BLOCK_SIZE, 2);
+
+        assert_eq!((result.iterations, result.warmup), (2, 1));
+        assert_eq!(
+            result.threads,
+            if cfg!(feature = "parallel") { 2 } else { 1 }
+        );
+        assert_eq!(result.bytes_per_iteration, config.size);
+        assert!(result.min_gbps > 0.0 && result.min_gbps <= result.max_gbps);
+    }
+}
@@ -0,0 +1,744 @@
+// src/block.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Deterministic block-fill core, without threads or a thread pool
+//!
+//! Everything the generators build on: the dedup and compression layout math,
+//! keystream seeding and the per-block fill. These functions only use `core` and
+//! `alloc` (plus `tracing` macros), take plain slices and keep no global state, so
+//! embedded or custom runtimes can drive parallelism themselves: fill block `i` of
+//! a dataset with
+//!
+//! ```rust
+//! use dgen_data::block::{compute_copy_lens, fill_block_at, scaled_copy_len, unique_block_count};
+//! use dgen_data::CompressibleFill;
+//!
+//! let (size, block_size, seed): (usize, usize, u64) = (3 * 1024 * 1024 + 100, 1024 * 1024, 42);
+//! let nblocks = size.div_ceil(block_size);
+//! let unique_blocks = unique_block_count(nblocks, 2);
+//! let copy_lens = compute_copy_lens(unique_blocks, block_size, 3);
+//!
+//! let mut data = vec![0u8; size];
+//! for (i, block) in data.chunks_mut(block_size).enumerate() {
+//!     let ub = i % unique_blocks;
+//!     let copy_len = scaled_copy_len(copy_lens[ub], block.len(), block_size);
+//!     let offset = (i * block_size) as u64;
+//!     fill_block_at(block, ub, copy_len, ub as u64, seed, CompressibleFill::Zeros, offset, &[]);
+//! }
+//! # #[cfg(feature = "generator")]
+//! # assert_eq!(
+//! #     data,
+//! #     dgen_data::generate_data(dgen_data::GeneratorConfig {
+//! #         size,
+//! #         dedup_factor: 2,
+//! #         compress_factor: 3,
+//! #         block_size: Some(block_size),
+//! #         seed: Some(seed),
+//! #         ..Default::default()
+//! #     })
+//! #     .as_slice()
+//! # );
+//! ```
+//!
+//! Build with `default-features = false, features = ["core"]` to get only this
+//! module and `KeyStream`, without rayon, hwloc or PyO3.
+
+use alloc::vec::Vec;
+
+use crate::constants::COMPRESSIBLE_TAG_INTERVAL;
+use crate::keystream::KeyStream;
+
+/// Contents of the compressible region at the end of each block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompressibleFill {
+    /// Zero bytes (default). Fastest, but the compressible regions of all blocks are
+    /// identical, so a page-level dedup engine collapses them across unique blocks
+    /// and reports a dedup ratio far above `dedup_factor` when `compress_factor` is high.
+    #[default]
+    Zeros,
+    /// Zero bytes with a 16-byte tag at the start of every `COMPRESSIBLE_TAG_INTERVAL`
+    /// (4 KiB) page of the region, derived from the seed and the unique block index.
+    /// Each unique block's compressible pages stay distinct while duplicates of it
+    /// remain identical, at the cost of 16 incompressible bytes per page (about 0.4%).
+    Tagged,
+    /// A `period`-byte keystream pattern derived from the seed and the unique block
+    /// index, repeated across the region. Compressible only by matching across the
+    /// repetition, so compressors whose window is shorter than `period` see random
+    /// data; models files whose redundancy is medium-range rather than runs of zeros.
+    Repeating { period: usize },
+    /// Zero bytes with a 16-byte tag at the start of every `COMPRESSIBLE_TAG_INTERVAL`
+    /// (4 KiB) page of the region: the page's absolute byte offset in the dataset
+    /// followed by a tag derived from the seed, both little-endian. Every
+    /// compressible page is unique, duplicates of a block included, so zero-page
+    /// elision and page dedup get nothing from these regions, and a verifier can
+    /// read back the offset each block was written at. Costs the same 16 bytes per
+    /// page as `Tagged`.
+    OffsetTagged,
+    /// Spans of a caller-supplied dictionary (the `dictionary` argument of
+    /// [`fill_block_at`]), picked by a keystream derived from the seed and the unique
+    /// block index. The region only holds byte sequences from the dictionary, so a
+    /// compressor primed with it (a trained zstd dictionary) finds long matches from
+    /// the first byte, while one without it only matches spans that recur within
+    /// its window. Zero bytes when the dictionary is empty. `GeneratorConfig`
+    /// selects this fill when `compress_dictionary` is set.
+    Dictionary,
+}
+
+/// Number of unique blocks for a given dedup factor (round-robin mapping)
+///
+/// `nblocks / dedup_factor` rounded half up, and at least 1. Block `i` of a
+/// dataset is a copy of unique block `i % unique_block_count(..)`.
+pub fn unique_block_count(nblocks: usize, dedup_factor: usize) -> usize {
+    if dedup_factor > 1 {
+        // Integer form of round(nblocks / dedup_factor): no floating point needed
+        let round_up = nblocks % dedup_factor >= dedup_factor - dedup_factor / 2;
+        (nblocks / dedup_factor + round_up as usize).max(1)
+    } else {
+        nblocks
+    }
+}
+
+/// Per-unique-block compressible byte counts for a compress factor
+///
+/// Uses integer error accumulation so compression is spread evenly across blocks.
+/// The products are taken in u128, so any factor works with any block size
+/// (`(factor - 1) * block_size` overflows usize on 32-bit targets at modest factors).
+pub fn compute_copy_lens(
+    unique_blocks: usize,
+    block_size: usize,
+    compress_factor: usize,
+) -> Vec<usize> {
+    let (f_num, f_den) = if compress_factor > 1 {
+        (compress_factor as u128 - 1, compress_factor as u128)
+    } else {
+        (0, 1)
+    };
+    distribute_copy_lens(unique_blocks, block_size, f_num, f_den)
+}
+
+/// Per-unique-block compressible byte counts for a compressible fraction
+///
+/// `fraction` of each block is compressible, clamped to [0, 1]. It is taken to
+/// 32 fractional bits and spread over the blocks with the same error accumulation
+/// as `compute_copy_lens()`, so the aggregate matches it to within a byte. A
+/// compress factor N is the fraction (N-1)/N.
+pub fn compute_copy_lens_fraction(
+    unique_blocks: usize,
+    block_size: usize,
+    fraction: f64,
+) -> Vec<usize> {
+    const ONE: u128 = 1 << 32;
+    let f_num = if fraction > 0.0 {
+        ((fraction * ONE as f64).round() as u128).min(ONE)
+    } else {
+        0 // Also NaN
+    };
+    distribute_copy_lens(unique_blocks, block_size, f_num, ONE)
+}
+
+/// `f_num / f_den` of each block, with the remainders carried from block to block
+fn distribute_copy_lens(
+    unique_blocks: usize,
+    block_size: usize,
+    f_num: u128,
+    f_den: u128,
+) -> Vec<usize> {
+    let floor_len = (f_num * block_size as u128 / f_den) as usize;
+    let rem = f_num * block_size as u128 % f_den;
+
+    let mut v = Vec::with_capacity(unique_blocks);
+    let mut err = 0u128;
+    for _ in 0..unique_blocks {
+        err += rem;
+        if err >= f_den {
+            err -= f_den;
+            v.push(floor_len + 1);
+        } else {
+            v.push(floor_len);
+        }
+    }
+    debug_assert!(
+        v.iter().all(|&len| len <= block_size),
+        "copy_len exceeds block_size {} for fraction {}/{}",
+        block_size,
+        f_num,
+        f_den
+    );
+    v
+}
+
+/// Compressible bytes for a block generated at `len` of its full `block_size`
+///
+/// A partial block keeps the same compressible fraction as a whole one, so a
+/// short final block doesn't skew the realized compress ratio.
+pub fn scaled_copy_len(copy_len: usize, len: usize, block_size: usize) -> usize {
+    if len >= block_size {
+        copy_len.min(len)
+    } else {
+        (copy_len as u64 * len as u64 / block_size as u64) as usize
+    }
+}
+
+/// Fill every block of `data` on the calling thread (no rayon overhead)
+///
+/// Block `i` is a copy of unique block `i % unique_blocks`, keystream sequence
+/// included, and a final partial block keeps the same compressible fraction, exactly as the
+/// parallel generator lays out a dataset starting at offset 0. Used for
+/// single-block requests and single-threaded configurations.
+pub fn fill_blocks_sequential(
+    data: &mut [u8],
+    block_size: usize,
+    unique_blocks: usize,
+    copy_lens: &[usize],
+    seed_base: u64,
+    fill: CompressibleFill,
+    dictionary: &[u8],
+) {
+    fill_blocks_sequential_with(
+        data,
+        block_size,
+        unique_blocks,
+        copy_lens,
+        seed_base,
+        (fill, dictionary),
+        false,
+    );
+}
+
+/// `fill_blocks_sequential()`, with keystream sequences passed through
+/// `mix_block_sequence()` when `mix_seeds` is set
+pub(crate) fn fill_blocks_sequential_with(
+    data: &mut [u8],
+    block_size: usize,
+    unique_blocks: usize,
+    copy_lens: &[usize],
+    seed_base: u64,
+    (fill, dictionary): (CompressibleFill, &[u8]),
+    mix_seeds: bool,
+) {
+    for (i, chunk) in data.chunks_mut(block_size).enumerate() {
+        let ub = i % unique_blocks;
+        fill_block_at(
+            chunk,
+            ub,
+            scaled_copy_len(copy_lens[ub], chunk.len(), block_size),
+            block_sequence(ub as u64, mix_seeds),
+            seed_base,
+            fill,
+            (i * block_size) as u64,
+            dictionary,
+        );
+    }
+}
+
+/// Fill one block of the dataset, starting at byte `offset`, with controlled compression
+///
+/// # Algorithm (OPTIMIZED January 2026)
+///
+/// **NEW METHOD (Current)**: Zero-fill for compression
+/// 1. Fill incompressible portion with Xoshiro256++ keystream (high-entropy random data)
+/// 2. Fill compressible portion with zeros (memset - extremely fast)
+///
+/// **OLD METHOD (Before Jan 2026)**: Back-reference approach
+/// - Filled entire block with RNG data
+/// - Created back-references using copy_within() in 64-256 byte chunks
+/// - SLOW: Required 2x memory traffic (write all, then copy 50% for 2:1 compression)
+/// - Example: 1 MB block @ 2:1 ratio = 1 MB RNG write + 512 KB of copy_within operations
+///
+/// **WHY CHANGED**:
+/// - Testing showed significant slowdown with compression enabled (1-4 GB/s vs 15 GB/s)
+/// - Back-references created small, inefficient memory copies
+/// - Zero-fill approach matches DLIO benchmark methodology
+/// - Much faster: memset is highly optimized (often CPU instruction or libc fast path)
+///
+/// **PERFORMANCE COMPARISON**:
+/// - Incompressible (copy_len=0): ~15 GB/s per core (both methods identical)
+/// - 2:1 compression (copy_len=50%): OLD ~2-4 GB/s, NEW ~10-12 GB/s (estimated)
+///
+/// # Parameters
+/// - `out`: Output buffer (BLOCK_SIZE bytes)
+/// - `unique_block_idx`: Index of unique block (for RNG seeding)
+/// - `copy_len`: Target bytes to make compressible (filled with zeros)
+/// - `block_sequence`: Keystream sequence for RNG derivation; the generators pass
+///   the unique block index, so every copy of a unique block gets the same keystream
+/// - `seed_base`: Base seed for this generation session
+/// - `fill`: Contents of the compressible region
+/// - `offset`: Byte offset of the block in the dataset; only
+///   `CompressibleFill::OffsetTagged` regions depend on it
+/// - `dictionary`: Dictionary of `CompressibleFill::Dictionary` regions (zeros when
+///   empty)
+///
+/// The offset is passed separately from `block_sequence` because duplicate blocks
+/// share a sequence but sit at different offsets.
+///
+/// # Determinism
+/// Output is identical on all platforms for the same inputs: the RNG is seeded via
+/// SplitMix64 (`seed_from_u64`) and `fill_bytes` emits each 64-bit word in
+/// little-endian order regardless of native endianness. No code path writes
+/// native-endian words. `test_keystream_golden_bytes` pins this layout.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_block_at(
+    out: &mut [u8],
+    unique_block_idx: usize,
+    copy_len: usize,
+    block_sequence: u64,
+    seed_base: u64,
+    fill: CompressibleFill,
+    offset: u64,
+    dictionary: &[u8],
+) {
+    // Records this block's fill duration when dropped, on every return path
+    #[cfg(feature = "block-timing")]
+    let _timer = crate::block_timing::BlockTimer::start();
+
+    tracing::trace!(
+        "fill_block_at: idx={}, seq={}, copy_len={}, out_len={}",
+        unique_block_idx,
+        block_sequence,
+        copy_len,
+        out.len()
+    );
+
+    // Fully compressible block: no random bytes needed, so skip RNG construction
+    if copy_len >= out.len() {
+        tracing::trace!(
+            "Filling {} bytes with zeros (fully compressible)",
+            out.len()
+        );
+        fill_compressible(
+            out,
+            0,
+            unique_block_idx,
+            seed_base,
+            (fill, dictionary),
+            offset,
+        );
+        return;
+    }
+
+    // Derive RNG from seed_base + sequential block number
+    // This ensures: same seed_base + same sequence → identical output
+    let mut rng = KeyStream::for_block(seed_base, block_sequence);
+
+    // OPTIMIZED COMPRESSION METHOD (January 2026):
+    // For compress_factor N:1 ratio, we want (N-1)/N of the block to be compressible
+    // Example: 2:1 ratio means 50% compressible, 4:1 means 75% compressible
+    //
+    // Strategy: Fill incompressible portion with RNG, compressible portion with zeros
+    // This is MUCH faster than the old back-reference approach
+
+    if copy_len == 0 {
+        // No compression: fill entire block with high-entropy random data
+        tracing::trace!(
+            "Filling {} bytes with RNG keystream (incompressible)",
+            out.len()
+        );
+        #[cfg(feature = "phase-timing")]
+        let start = std::time::Instant::now();
+        rng.fill(out);
+        #[cfg(feature = "phase-timing")]
+        crate::phase_timing::record_keystream(start.elapsed(), out.len());
+    } else {
+        // With compression: split between random and zeros
+        let incompressible_len = out.len().saturating_sub(copy_len);
+
+        tracing::trace!(
+            "Filling block: {} bytes random (incompressible) + {} bytes zeros (compressible)",
+            incompressible_len,
+            copy_len
+        );
+
+        // Step 1: Fill incompressible portion with high-entropy keystream
+        if incompressible_len > 0 {
+            #[cfg(feature = "phase-timing")]
+            let start = std::time::Instant::now();
+            rng.fill(&mut out[..incompressible_len]);
+            #[cfg(feature = "phase-timing")]
+            crate::phase_timing::record_keystream(start.elapsed(), incompressible_len);
+        }
+
+        // Step 2: Fill compressible portion with zeros (memset - super fast!)
+        // This is typically optimized to a CPU instruction or fast libc call
+        if copy_len > 0 && incompressible_len < out.len() {
+            fill_compressible(
+                out,
+                incompressible_len,
+                unique_block_idx,
+                seed_base,
+                (fill, dictionary),
+                offset,
+            );
+        }
+    }
+
+    tracing::trace!(
+        "fill_block_at complete: {} compressible bytes (zeros)",
+        copy_len
+    );
+}
+
+/// Fill the compressible region `block[start..]` according to `fill`
+///
+/// `offset` is the block's byte offset in the dataset.
+fn fill_compressible(
+    block: &mut [u8],
+    start: usize,
+    unique_block_idx: usize,
+    seed_base: u64,
+    (fill, dictionary): (CompressibleFill, &[u8]),
+    offset: u64,
+) {
+    match fill {
+        CompressibleFill::Repeating { period } => {
+            repeat_pattern(&mut block[start..], period, unique_block_idx, seed_base);
+            return;
+        }
+        CompressibleFill::Dictionary if !dictionary.is_empty() => {
+            copy_dictionary_spans(&mut block[start..], dictionary, unique_block_idx, seed_base);
+            return;
+        }
+        _ => {}
+    }
+
+    #[cfg(feature = "phase-timing")]
+    let timer = std::time::Instant::now();
+    block[start..].fill(0);
+    #[cfg(feature = "phase-timing")]
+    crate::phase_timing::record_zero_fill(timer.elapsed(), block.len() - start);
+    match fill {
+        CompressibleFill::Tagged => {
+            tag_compressible_pages(block, start, unique_block_idx, seed_base)
+        }
+        CompressibleFill::OffsetTagged => tag_page_offsets(block, start, offset, seed_base),
+        _ => {}
+    }
+}
+
+/// Mixed into the seed to derive unique block prefixes, so a prefix never
+/// repeats the keystream of a block
+const UNIQUE_PREFIX_SALT: u64 = 0x7072_6566_6978_6573; // "prefixes"
+
+/// Overwrite the first `prefix_len` bytes of every block overlapping `buf`
+///
+/// `buf` holds the dataset bytes starting at `offset`, laid out in blocks of
+/// `block_size`. Each prefix is keystream seeded by the seed and the block's
+/// absolute index (not its unique block), so duplicate blocks differ in their
+/// prefixes and match everywhere else. A prefix cut by either end of `buf` is
+/// generated whole and the covered bytes copied, so the result does not depend
+/// on how the dataset is split into ranges.
+pub fn stamp_unique_prefixes(
+    buf: &mut [u8],
+    offset: u64,
+    block_size: usize,
+    prefix_len: usize,
+    seed_base: u64,
+) {
+    let prefix_len = prefix_len.min(block_size);
+    if prefix_len == 0 || buf.is_empty() {
+        return;
+    }
+    let end = offset + buf.len() as u64;
+    let mut scratch = Vec::new();
+    let mut block = offset / block_size as u64;
+    while block * (block_size as u64) < end {
+        let start = block * block_size as u64;
+        let (lo, hi) = (start.max(offset), (start + prefix_len as u64).min(end));
+        if lo < hi {
+            let out = &mut buf[(lo - offset) as usize..(hi - offset) as usize];
+            let mut rng = KeyStream::new(splitmix64(
+                seed_base.wrapping_add(block) ^ UNIQUE_PREFIX_SALT,
+            ));
+            if out.len() == prefix_len {
+                rng.fill(out);
+            } else {
+                scratch.resize(prefix_len, 0);
+                rng.fill(&mut scratch);
+                let from = (lo - start) as usize;
+                out.copy_from_slice(&scratch[from..from + out.len()]);
+            }
+        }
+        block += 1;
+    }
+}
+
+/// Mixed into the seed to derive `CompressibleFill::Repeating` patterns, so a
+/// pattern never starts like the keystream of a block
+const REPEATING_PATTERN_SALT: u64 = 0x7265_7065_6174_6572; // "repeater"
+
+/// Fill `region` with a repeating `period`-byte pattern for unique block `unique_block_idx`
+///
+/// The first period is drawn from a keystream seeded by the seed and unique block
+/// index (so duplicate blocks stay identical), then doubled in place with
+/// `copy_within` instead of being regenerated.
+fn repeat_pattern(region: &mut [u8], period: usize, unique_block_idx: usize, seed_base: u64) {
+    let period = period.clamp(1, region.len().max(1));
+    let seed = splitmix64(seed_base.wrapping_add(unique_block_idx as u64) ^ REPEATING_PATTERN_SALT);
+    let first = period.min(region.len());
+    KeyStream::new(seed).fill(&mut region[..first]);
+
+    // `filled` stays a multiple of `period` until the final, possibly partial, copy
+    let mut filled = first;
+    while filled < region.len() {
+        let n = filled.min(region.len() - filled);
+        region.copy_within(..n, filled);
+        filled += n;
+    }
+}
+
+/// Mixed into the seed to pick `CompressibleFill::Dictionary` spans
+const DICTIONARY_SPAN_SALT: u64 = 0x6469_6374_7370_616e; // "dictspan"
+
+/// Shortest and longest dictionary span copied into a compressible region. Long
+/// enough for any LZ match finder, short enough that a region mixes many spans.
+const DICTIONARY_SPAN_LEN: (usize, usize) = (16, 256);
+
+/// Fill `region` with spans of `dictionary` for unique block `unique_block_idx`
+///
+/// Each span starts at a random dictionary offset and runs for a random length
+/// in `DICTIONARY_SPAN_LEN`, cut short at the end of the dictionary. The spans
+/// depend only on the seed and `unique_block_idx`, so duplicate blocks stay
+/// identical.
+fn copy_dictionary_spans(
+    region: &mut [u8],
+    dictionary: &[u8],
+    unique_block_idx: usize,
+    seed_base: u64,
+) {
+    let (min_len, max_len) = DICTIONARY_SPAN_LEN;
+    let seed = splitmix64(seed_base.wrapping_add(unique_block_idx as u64) ^ DICTIONARY_SPAN_SALT);
+    let mut rng = KeyStream::new(seed);
+    let mut pos = 0;
+    while pos < region.len() {
+        let r = rng.next_u64();
+        let start = (r % dictionary.len() as u64) as usize;
+        let span = min_len + ((r >> 32) as usize) % (max_len - min_len + 1);
+        let n = span.min(dictionary.len() - start).min(region.len() - pos);
+        region[pos..pos + n].copy_from_slice(&dictionary[start..start + n]);
+        pos += n;
+    }
+}
+
+/// Mixed into the seed to derive compressible-region tags, so a tag never equals
+/// the seed of a keystream block
+const COMPRESSIBLE_TAG_SALT: u64 = 0x7a65_726f_7461_6773; // "zerotags"
+
+/// Write `CompressibleFill::Tagged` tags into the zero-filled region `block[start..]`
+///
+/// Each block-relative page boundary at or after `start` gets 8 bytes identifying
+/// the unique block followed by the 8-byte page index, both little-endian and
+/// truncated at the end of the block. The tags depend only on the seed and
+/// `unique_block_idx`, so duplicate blocks stay byte-identical.
+fn tag_compressible_pages(block: &mut [u8], start: usize, unique_block_idx: usize, seed_base: u64) {
+    let block_tag =
+        splitmix64(seed_base.wrapping_add(unique_block_idx as u64) ^ COMPRESSIBLE_TAG_SALT);
+    let first_page = start.div_ceil(COMPRESSIBLE_TAG_INTERVAL);
+    for page in first_page..block.len().div_ceil(COMPRESSIBLE_TAG_INTERVAL) {
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&block_tag.to_le_bytes());
+        tag[8..].copy_from_slice(&(page as u64).to_le_bytes());
+        let pos = page * COMPRESSIBLE_TAG_INTERVAL;
+        let len = tag.len().min(block.len() - pos);
+        block[pos..pos + len].copy_from_slice(&tag[..len]);
+    }
+}
+
+/// Mixed into the seed to derive `CompressibleFill::OffsetTagged` tags
+const OFFSET_TAG_SALT: u64 = 0x6f66_6673_6574_7467; // "offsettg"
+
+/// Write `CompressibleFill::OffsetTagged` tags into the zero-filled region `block[start..]`
+///
+/// Each block-relative page boundary at or after `start` gets the page's dataset
+/// offset (`block_offset` plus its position in the block) followed by a per-seed
+/// tag, both little-endian and truncated at the end of the block.
+fn tag_page_offsets(block: &mut [u8], start: usize, block_offset: u64, seed_base: u64) {
+    let seed_tag = splitmix64(seed_base ^ OFFSET_TAG_SALT);
+    let first_page = start.div_ceil(COMPRESSIBLE_TAG_INTERVAL);
+    for page in first_page..block.len().div_ceil(COMPRESSIBLE_TAG_INTERVAL) {
+        let pos = page * COMPRESSIBLE_TAG_INTERVAL;
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&block_offset.wrapping_add(pos as u64).to_le_bytes());
+        tag[8..].copy_from_slice(&seed_tag.to_le_bytes());
+        let len = tag.len().min(block.len() - pos);
+        block[pos..pos + len].copy_from_slice(&tag[..len]);
+    }
+}
+
+/// Mixed into block sequences by `mix_block_sequence()`
+const BLOCK_SEQUENCE_SALT: u64 = 0x626c_6f63_6b73_6571; // "blockseq"
+
+/// Keystream sequence of a block with `GeneratorConfig::mix_block_seeds` set
+///
+/// Hashes the sequence with SplitMix64 before it is added to the seed, so the
+/// blocks of a stream no longer seed their keystreams from consecutive integers.
+/// `KeyStream` seeding applies SplitMix64 again, making a two-stage cascade.
+pub fn mix_block_sequence(sequence: u64) -> u64 {
+    splitmix64(sequence ^ BLOCK_SEQUENCE_SALT)
+}
+
+/// `sequence`, through `mix_block_sequence()` when `mix` is set
+pub(crate) fn block_sequence(sequence: u64, mix: bool) -> u64 {
+    if mix {
+        mix_block_sequence(sequence)
+    } else {
+        sequence
+    }
+}
+
+/// SplitMix64 finalizer: a fast, well-mixed hash of a 64-bit value
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_copy_lens_fraction() {
+        let block_size = 1024 * 1024 + 7;
+        for fraction in [0.0, 0.3, 0.7, 0.999] {
+            let lens = compute_copy_lens_fraction(1000, block_size, fraction);
+            let total: usize = lens.iter().sum();
+            let target = fraction * (1000 * block_size) as f64;
+            assert!(
+                (total as f64 - target).abs() <= 1.0,
+                "{}: {}",
+                fraction,
+                total
+            );
+            let min = *lens.iter().min().unwrap();
+            assert!(lens.iter().all(|&len| len - min <= 1));
+        }
+        // A factor N and the fraction (N-1)/N agree
+        assert_eq!(
+            compute_copy_lens_fraction(10, 1 << 20, 0.75),
+            compute_copy_lens(10, 1 << 20, 4)
+        );
+        // Clamped to 1, which leaves no incompressible bytes
+        assert_eq!(compute_copy_lens_fraction(3, 4096, 1.0), vec![4096; 3]);
+        assert_eq!(compute_copy_lens_fraction(3, 4096, 1.5), vec![4096; 3]);
+        assert_eq!(compute_copy_lens_fraction(2, 4096, f64::NAN), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_unique_block_count_rounding() {
+        // Matches the original floating-point round(nblocks / dedup_factor)
+        for nblocks in 0..200usize {
+            for dedup in 1..40usize {
+                let float = if dedup > 1 {
+                    ((nblocks as f64) / (dedup as f64)).round().max(1.0) as usize
+                } else {
+                    nblocks
+                };
+                assert_eq!(
+                    unique_block_count(nblocks, dedup),
+                    float,
+                    "{} / {}",
+                    nblocks,
+                    dedup
+                );
+            }
+        }
+        assert_eq!(unique_block_count(5, usize::MAX), 1);
+    }
+
+    #[test]
+    fn test_fill_blocks_sequential_layout() {
+        let block_size = 4096;
+        let unique_blocks = unique_block_count(5, 2);
+        let copy_lens = compute_copy_lens(unique_blocks, block_size, 2);
+        let mut data = vec![0xAAu8; 5 * block_size - 100];
+        fill_blocks_sequential(
+            &mut data,
+            block_size,
+            unique_blocks,
+            &copy_lens,
+            7,
+            CompressibleFill::Zeros,
+            &[],
+        );
+
+        // Each block is its unique block's keystream sequence followed by zeros
+        for (i, block) in data.chunks(block_size).enumerate() {
+            let copy_len = scaled_copy_len(copy_lens[i % unique_blocks], block.len(), block_size);
+            let random_len = block.len() - copy_len;
+            let mut expected = vec![0u8; random_len];
+            KeyStream::for_block(7, (i % unique_blocks) as u64).fill(&mut expected);
+            assert_eq!(&block[..random_len], &expected[..], "block {}", i);
+            assert!(block[random_len..].iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn test_repeating_compressible_fill() {
+        let (len, copy_len, period) = (8192, 6000, 1000);
+        let fill = CompressibleFill::Repeating { period };
+        let mut block = vec![0u8; len];
+        fill_block_at(&mut block, 3, copy_len, 9, 5, fill, 0, &[]);
+
+        let region = &block[len - copy_len..];
+        assert!(region.iter().any(|&b| b != 0));
+        assert!((period..copy_len).all(|k| region[k] == region[k - period]));
+        assert_ne!(
+            &region[..8],
+            &block[..8],
+            "pattern must not repeat the keystream"
+        );
+
+        // The pattern follows the unique block, not the block sequence
+        let mut dup = vec![0u8; len];
+        fill_block_at(&mut dup, 3, copy_len, 10, 5, fill, 0, &[]);
+        assert_eq!(&dup[len - copy_len..], region);
+        fill_block_at(&mut dup, 4, copy_len, 10, 5, fill, 0, &[]);
+        assert_ne!(&dup[len - copy_len..], region);
+
+        // A fully compressible block is all pattern, a period larger than the region is truncated
+        let mut full = vec![0u8; 4096];
+        fill_block_at(
+            &mut full,
+            3,
+            4096,
+            0,
+            5,
+            CompressibleFill::Repeating { period: 1 << 20 },
+            0,
+            &[],
+        );
+        assert_eq!(&full[..period], &region[..period]);
+    }
+
+    #[test]
+    fn test_mixed_block_seeds_randomness() {
+        // First word of 4096 consecutive blocks: the bits a cross-block test sees
+        let words: Vec<u64> = (0..4096)
+            .map(|i| KeyStream::for_block(0, mix_block_sequence(i)).next_u64())
+            .collect();
+        let n = (words.len() * 64) as f64;
+        let sd = n.sqrt() / 2.0;
+
+        // Monobit: ones within 5 standard deviations of n / 2
+        let ones: u32 = words.iter().map(|w| w.count_ones()).sum();
+        let ones = ones as f64;
+        assert!((ones - n / 2.0).abs() < 5.0 * sd, "{} ones", ones);
+
+        // Runs: bit changes within 5 standard deviations of the expected count
+        let bits = || {
+            words
+                .iter()
+                .flat_map(|w| (0..64).map(move |k| (w >> k) & 1))
+        };
+        let runs = 1 + bits().zip(bits().skip(1)).filter(|(a, b)| a != b).count();
+        let expected = 2.0 * ones * (n - ones) / n + 1.0;
+        assert!((runs as f64 - expected).abs() < 5.0 * sd, "{} runs", runs);
+
+        // Mixing changes every block's seed but no sequence maps to another's
+        assert_ne!(mix_block_sequence(0), 0);
+        assert_ne!(block_sequence(1, true), block_sequence(1, false));
+        assert_eq!(block_sequence(1, false), 1);
+    }
+}
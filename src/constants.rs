@@ -8,14 +8,93 @@
 pub const BLOCK_SIZE: usize = 1024 * 1024;
 
 /// Minimum size for data generation (one block)
+///
+/// Not referenced by the generator: requests are padded to one block of the
+/// configured size (`GeneratorConfig::auto_block_size()`), which is not
+/// necessarily `BLOCK_SIZE`.
+#[deprecated(
+    since = "0.3.0",
+    note = "the minimum is one block of GeneratorConfig::auto_block_size(), not this constant"
+)]
 pub const MIN_SIZE: usize = BLOCK_SIZE;
 
 /// Maximum back-reference distance for compression (1 KiB)
-/// Keeps compression patterns local for realistic compressor behavior
+///
+/// Only used by the back-reference compression method, which was replaced by
+/// zero-fill in January 2026 (see `fill_block`). No longer affects generation.
+#[deprecated(
+    since = "0.3.0",
+    note = "back-reference compression was removed; compressibility is set by compress_factor"
+)]
 pub const MAX_BACK_REF_DISTANCE: usize = 1024;
 
 /// Minimum run length for back-references (64 bytes)
+///
+/// No longer affects generation (see `MAX_BACK_REF_DISTANCE`).
+#[deprecated(
+    since = "0.3.0",
+    note = "back-reference compression was removed; compressibility is set by compress_factor"
+)]
 pub const MIN_RUN_LENGTH: usize = 64;
 
 /// Maximum run length for back-references (256 bytes)
+///
+/// No longer affects generation (see `MAX_BACK_REF_DISTANCE`).
+#[deprecated(
+    since = "0.3.0",
+    note = "back-reference compression was removed; compressibility is set by compress_factor"
+)]
 pub const MAX_RUN_LENGTH: usize = 256;
+
+/// Smallest internal block size (256 KiB)
+/// `GeneratorConfig::auto_block_size()` goes this low for small unseeded requests
+/// on many cores, and explicit block sizes are clamped to it (to `BLOCK_SIZE` in
+/// data format versions 1 and 2).
+pub const MIN_BLOCK_SIZE: usize = 256 * 1024;
+
+/// Largest internal block size (32 MiB)
+pub const MAX_BLOCK_SIZE: usize = 32 * 1024 * 1024;
+
+/// Minimum number of blocks per thread targeted by `GeneratorConfig::auto_block_size()`
+/// Block sizes are rounded down to a power of two, giving 4-8 blocks per thread.
+pub const TARGET_BLOCKS_PER_THREAD: usize = 4;
+
+/// Page size assumed for NUMA first-touch (4 KiB)
+/// One byte per page is written so every page faults in on the touching thread;
+/// with larger (huge) pages this just touches each one several times.
+pub const PAGE_SIZE: usize = 4096;
+
+/// Size and address alignment required by O_DIRECT on 4 KiB-sector devices
+/// Used by `GeneratorConfig::nvme_defaults()`; also satisfies 512-byte sectors.
+pub const DIRECT_IO_ALIGNMENT: usize = 4096;
+
+/// Block size used by `GeneratorConfig::nvme_defaults()` (1 MiB)
+/// NVMe drives reach full sequential bandwidth at 128 KiB-1 MiB per write.
+pub const NVME_WRITE_SIZE: usize = 1024 * 1024;
+
+/// Spacing of the tags written by `CompressibleFill::Tagged` (4 KiB)
+/// Matches the page size most dedup engines fingerprint at, so every compressible
+/// page of a unique block carries its own tag.
+pub const COMPRESSIBLE_TAG_INTERVAL: usize = 4096;
+
+/// Largest `generate_data_simple()` request generated inline on the calling thread (4 MiB)
+/// Below this, spawning a thread pool costs more than it saves.
+pub const SIMPLE_INLINE_MAX_SIZE: usize = 4 * BLOCK_SIZE;
+
+/// Burst allowed by `GeneratorConfig::max_gbps` pacing (100 ms at the configured rate)
+/// A stream idle for longer than this catches up by at most this much data.
+pub const RATE_LIMIT_BURST: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Version of the generated data layout (RNG seeding, keystream and compression fill)
+///
+/// Bumped whenever a change would alter the bytes produced for a given seed and
+/// configuration. Record it alongside archived seeds; `GeneratorConfig::format_version`
+/// selects the layout to reproduce. Version 1: per-block Xoshiro256++ seeded via
+/// SplitMix64 from `seed + block_sequence`, compressible bytes zero-filled at the
+/// end of each block. Version 2: a partial final block is generated at its valid
+/// length with proportionally scaled compressible bytes, instead of generated whole
+/// and truncated. Version 3: the keystream sequence is the unique block index, not
+/// the block number, so duplicate blocks are byte-identical (identical to version 2
+/// without dedup), CSV rows and dictionary slots restart at every block, and
+/// explicit block sizes down to `MIN_BLOCK_SIZE` are kept instead of raised to 1 MiB.
+pub const DATA_FORMAT_VERSION: u32 = 3;
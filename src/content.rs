@@ -0,0 +1,554 @@
+// src/content.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Structured content modes layered over the generated keystream
+//!
+//! Generation always produces keystream bytes followed by a zero-filled
+//! compressible region. A content mode then maps those bytes, by their position
+//! within their block, into a structured format. Because the mapping only depends
+//! on the byte value (or the seed and unique block) and its position, any range
+//! can be transformed independently, streaming, random-access and one-shot
+//! generation agree, and duplicate blocks stay byte-identical.
+
+#[cfg(not(feature = "parallel"))]
+use crate::sequential as rayon;
+use rand::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use rayon::prelude::*;
+
+use crate::block::splitmix64;
+use crate::generator::ConfigError;
+
+/// Printable symbols for CSV fields (URL-safe base64 alphabet)
+///
+/// 64 symbols so each keystream byte maps to 6 bits of field entropy. Zero bytes
+/// from the compressible region map to `A`, producing runs of repeated fields.
+const FIELD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Default column count for `"csv"` without parameters
+pub const DEFAULT_CSV_COLUMNS: usize = 8;
+
+/// Default field width for `"csv"` without parameters
+pub const DEFAULT_CSV_FIELD_LEN: usize = 16;
+
+/// Default number of distinct values for `"dict"` without parameters
+pub const DEFAULT_DICT_VALUES: usize = 256;
+
+/// Default value width for `"dict"` without parameters
+pub const DEFAULT_DICT_VALUE_LEN: usize = 16;
+
+/// Default pattern period for `"pseudo"` without parameters: twice the 32 KiB
+/// DEFLATE window, and just past LZ4's 64 KiB match distance
+pub const DEFAULT_PSEUDO_PERIOD: usize = 64 * 1024;
+
+/// Compressor family a `PseudoCompressible` pattern is tuned for
+///
+/// LZ-style compressors only find a repeat within their match window, so the
+/// pattern period decides which compressors realize the requested ratio. Each
+/// target picks a period inside its family's window (with margin for lazy and
+/// hash-chain matching); data tuned for a long-window compressor stays close to
+/// incompressible for the short-window ones. The first `period` bytes of every
+/// compressible region are literals for any compressor, so shorter periods track
+/// `compress_factor` more closely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressTarget {
+    /// gzip/zlib: 16 KiB period, inside the 32 KiB DEFLATE window
+    Deflate,
+    /// zstd: 128 KiB period, past the DEFLATE and LZ4 windows but inside zstd's
+    /// window at every positive level (512 KiB and up)
+    Zstd,
+    /// LZ4: 32 KiB period, inside its 64 KiB match distance
+    Lz4,
+    /// Any LZ compressor: 8 KiB period, inside every common window
+    Generic,
+}
+
+impl CompressTarget {
+    /// Pattern period (repetition distance) in bytes for this target
+    pub fn repeat_period(self) -> usize {
+        match self {
+            CompressTarget::Deflate => 16 * 1024,
+            CompressTarget::Zstd => 128 * 1024,
+            CompressTarget::Lz4 => 32 * 1024,
+            CompressTarget::Generic => 8 * 1024,
+        }
+    }
+}
+
+impl From<CompressTarget> for ContentMode {
+    /// `PseudoCompressible` with the target's period
+    fn from(target: CompressTarget) -> Self {
+        ContentMode::PseudoCompressible {
+            period: target.repeat_period(),
+        }
+    }
+}
+
+impl std::str::FromStr for CompressTarget {
+    type Err = ConfigError;
+
+    /// Parse `"deflate"` (or `"gzip"`/`"zlib"`), `"zstd"`, `"lz4"` or `"generic"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "deflate" | "gzip" | "zlib" => Ok(CompressTarget::Deflate),
+            "zstd" => Ok(CompressTarget::Zstd),
+            "lz4" => Ok(CompressTarget::Lz4),
+            "generic" => Ok(CompressTarget::Generic),
+            _ => Err(ConfigError::InvalidContentMode(format!(
+                "'{}': expected compress target 'deflate', 'zstd', 'lz4' or 'generic'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Mixed into the dataset seed to derive the dictionary table, so the table
+/// differs from the keystream of block 0
+const DICTIONARY_SEED_SALT: u64 = 0x6469_6374_7461_626c; // "dicttabl"
+
+/// Mixed with the unique block index to derive a block's dictionary choices
+const DICTIONARY_BLOCK_SALT: u64 = 0x6469_6374_626c_6f63; // "dictbloc"
+
+/// Bytes per parallel work unit when transforming a buffer
+const CONTENT_CHUNK: usize = 1024 * 1024;
+
+/// Layout of the generated bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContentMode {
+    /// Raw keystream bytes with zero-filled compressible regions (default)
+    #[default]
+    Random,
+    /// Comma-separated rows of `columns` printable fields, each `field_len` bytes,
+    /// terminated by `\n`. Rows are laid out from the start of every block, so
+    /// every row is `columns * (field_len + 1)` bytes except the last of a block,
+    /// which is cut short and ends with `\n` on the block's last byte. Duplicate
+    /// blocks therefore stay byte-identical and `dedup_factor` applies as in
+    /// `Random` mode. (Format versions before 3 lay rows out across the whole
+    /// dataset instead.)
+    ///
+    /// Field bytes are derived from the keystream, so the text itself compresses
+    /// roughly 1.3:1 (6 bits of entropy per byte). The compressible region becomes
+    /// runs of identical `AAAA…` fields, modeling repeated / low-cardinality columns.
+    Csv { columns: usize, field_len: usize },
+    /// Consecutive `value_len`-byte record slots, each holding one of `num_values`
+    /// printable values. The values are derived from the seed and each slot's
+    /// choice from the seed, the unique block and the slot's position in the block,
+    /// so the data is reproducible and dictionary/RLE encodable: fewer values
+    /// compress better. Slots restart at every block (the last slot of a block is
+    /// cut short), and duplicate blocks are byte-identical, so `dedup_factor`
+    /// applies as in `Random` mode. Compressibility is governed by `num_values`
+    /// (and `value_len`); `compress_factor` has no effect in this mode. (Format
+    /// versions before 3 choose values by dataset-wide slot, ignoring
+    /// `dedup_factor`.)
+    Dictionary { num_values: usize, value_len: usize },
+    /// Raw keystream bytes whose compressible regions repeat a `period`-byte random
+    /// pattern instead of holding zeros (`CompressibleFill::Repeating`, overriding
+    /// `compressible_fill`). The regions are only compressible by a match finder
+    /// whose window reaches back `period` bytes, so ratios differ by compressor and
+    /// level instead of every compressor eliding the zeros. Use
+    /// `ContentMode::from(CompressTarget)` to pick the period for a compressor.
+    PseudoCompressible { period: usize },
+}
+
+impl ContentMode {
+    /// Check the mode's parameters
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match *self {
+            ContentMode::Random => Ok(()),
+            ContentMode::PseudoCompressible { period } => {
+                if period == 0 {
+                    Err(ConfigError::InvalidContentMode(
+                        "pseudo period must be non-zero".to_string(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            ContentMode::Csv { columns, field_len } => {
+                if columns == 0 || field_len == 0 {
+                    Err(ConfigError::InvalidContentMode(format!(
+                        "csv columns ({}) and field_len ({}) must be non-zero",
+                        columns, field_len
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+            ContentMode::Dictionary {
+                num_values,
+                value_len,
+            } => {
+                if num_values == 0 || value_len == 0 {
+                    Err(ConfigError::InvalidContentMode(format!(
+                        "dictionary num_values ({}) and value_len ({}) must be non-zero",
+                        num_values, value_len
+                    )))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ContentMode {
+    type Err = ConfigError;
+
+    /// Parse `"random"`, `"csv[:COLUMNS:FIELD_LEN]"`, `"dict[:NUM_VALUES:VALUE_LEN]"`
+    /// or `"pseudo[:PERIOD|TARGET]"`, where `TARGET` is a [`CompressTarget`] name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ConfigError::InvalidContentMode(format!(
+                "'{}': expected 'random', 'csv[:COLUMNS:FIELD_LEN]', \
+                 'dict[:NUM_VALUES:VALUE_LEN]' or 'pseudo[:PERIOD|TARGET]'",
+                s
+            ))
+        };
+
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or("").to_lowercase();
+        let mut param = |default: usize| match parts.next() {
+            Some(p) => p.parse().map_err(|_| invalid()),
+            None => Ok(default),
+        };
+        let mode = match name.as_str() {
+            "random" => ContentMode::Random,
+            "csv" => ContentMode::Csv {
+                columns: param(DEFAULT_CSV_COLUMNS)?,
+                field_len: param(DEFAULT_CSV_FIELD_LEN)?,
+            },
+            "dict" | "dictionary" => ContentMode::Dictionary {
+                num_values: param(DEFAULT_DICT_VALUES)?,
+                value_len: param(DEFAULT_DICT_VALUE_LEN)?,
+            },
+            "pseudo" | "pseudocompressible" => match parts.next() {
+                Some(p) => match p.parse::<CompressTarget>() {
+                    Ok(target) => target.into(),
+                    Err(_) => ContentMode::PseudoCompressible {
+                        period: p.parse().map_err(|_| invalid())?,
+                    },
+                },
+                None => ContentMode::PseudoCompressible {
+                    period: DEFAULT_PSEUDO_PERIOD,
+                },
+            },
+            _ => return Err(invalid()),
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        mode.validate()?;
+        Ok(mode)
+    }
+}
+
+/// A content mode prepared for one dataset seed
+///
+/// Holds any per-dataset state (the dictionary table), so it is built once per
+/// generation and then applied to every range of that dataset.
+#[derive(Debug, Clone)]
+pub(crate) struct ContentFill {
+    content: ContentMode,
+    seed: u64,
+    /// `num_values * value_len` printable bytes for `ContentMode::Dictionary`
+    dictionary: Vec<u8>,
+    /// (block size, unique blocks) to lay out per block; None = across the dataset
+    blocks: Option<(u64, u64)>,
+}
+
+impl ContentFill {
+    pub(crate) fn new(content: ContentMode, seed: u64) -> Self {
+        let dictionary = match content {
+            ContentMode::Dictionary {
+                num_values,
+                value_len,
+            } => {
+                let mut table = vec![0u8; num_values.max(1) * value_len.max(1)];
+                Xoshiro256PlusPlus::seed_from_u64(seed ^ DICTIONARY_SEED_SALT)
+                    .fill_bytes(&mut table);
+                for b in table.iter_mut() {
+                    *b = FIELD_ALPHABET[(*b & 63) as usize];
+                }
+                table
+            }
+            _ => Vec::new(),
+        };
+        Self {
+            content,
+            seed,
+            dictionary,
+            blocks: None,
+        }
+    }
+
+    /// Lay rows and slots out per block of `block_size`, keying dictionary choices
+    /// on the unique block (of `unique_blocks`) so duplicate blocks match
+    pub(crate) fn per_block(mut self, block_size: usize, unique_blocks: usize) -> Self {
+        self.blocks = Some((block_size.max(1) as u64, unique_blocks.max(1) as u64));
+        self
+    }
+
+    /// Same layout for another dataset seed
+    pub(crate) fn reseeded(&self, seed: u64) -> Self {
+        Self {
+            blocks: self.blocks,
+            ..Self::new(self.content, seed)
+        }
+    }
+
+    /// Map generated bytes at dataset offset `offset` into the content layout, in place
+    ///
+    /// No-op for `ContentMode::Random` and `PseudoCompressible`, whose layout is
+    /// produced by the block fill. With `parallel`, the buffer is split across
+    /// the current rayon pool.
+    pub(crate) fn apply(&self, buf: &mut [u8], offset: u64, parallel: bool) {
+        if matches!(
+            self.content,
+            ContentMode::Random | ContentMode::PseudoCompressible { .. }
+        ) {
+            return;
+        }
+
+        if parallel && buf.len() > CONTENT_CHUNK {
+            buf.par_chunks_mut(CONTENT_CHUNK)
+                .enumerate()
+                .for_each(|(i, chunk)| {
+                    self.apply_chunk(chunk, offset + (i * CONTENT_CHUNK) as u64)
+                });
+        } else {
+            self.apply_chunk(buf, offset);
+        }
+    }
+
+    /// Transform `buf` at dataset offset `offset`, one block's share at a time
+    fn apply_chunk(&self, buf: &mut [u8], mut offset: u64) {
+        let block_size = self.blocks.map_or(u64::MAX, |(block_size, _)| block_size);
+        let mut rest = buf;
+        while !rest.is_empty() {
+            let pos = offset % block_size;
+            let n = (block_size - pos).min(rest.len() as u64) as usize;
+            let (segment, tail) = std::mem::take(&mut rest).split_at_mut(n);
+            self.apply_block(segment, offset / block_size, pos);
+            rest = tail;
+            offset += n as u64;
+        }
+    }
+
+    /// Transform bytes of block `block` from position `pos` within it
+    fn apply_block(&self, buf: &mut [u8], block: u64, pos: u64) {
+        match self.content {
+            ContentMode::Random | ContentMode::PseudoCompressible { .. } => {}
+            ContentMode::Csv { columns, field_len } => {
+                let field_stride = field_len.max(1) + 1;
+                let row_len = columns.max(1) * field_stride;
+                let mut col = (pos % row_len as u64) as usize;
+                // A block's last byte ends its (possibly short) final row
+                let block_end = self
+                    .blocks
+                    .map(|(block_size, _)| (block_size - pos - 1) as usize);
+                for (i, b) in buf.iter_mut().enumerate() {
+                    *b = if col + 1 == row_len || Some(i) == block_end {
+                        b'\n'
+                    } else if (col + 1).is_multiple_of(field_stride) {
+                        b','
+                    } else {
+                        FIELD_ALPHABET[(*b & 63) as usize]
+                    };
+                    col += 1;
+                    if col == row_len {
+                        col = 0;
+                    }
+                }
+            }
+            ContentMode::Dictionary {
+                num_values,
+                value_len,
+            } => {
+                let value_len = value_len.max(1);
+                let num_values = num_values.max(1) as u64;
+                // Choices follow the unique block, so duplicate blocks match
+                let key = match self.blocks {
+                    Some((_, unique_blocks)) => splitmix64(
+                        self.seed ^ splitmix64((block % unique_blocks) ^ DICTIONARY_BLOCK_SALT),
+                    ),
+                    None => self.seed,
+                };
+                let mut slot = pos / value_len as u64;
+                let mut pos = (pos % value_len as u64) as usize;
+                let mut out = &mut buf[..];
+                while !out.is_empty() {
+                    let value = (splitmix64(key ^ slot) % num_values) as usize;
+                    let src = &self.dictionary[value * value_len..(value + 1) * value_len];
+                    let n = (value_len - pos).min(out.len());
+                    out[..n].copy_from_slice(&src[pos..pos + n]);
+                    out = &mut out[n..];
+                    slot += 1;
+                    pos = 0;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_content_mode() {
+        assert_eq!("random".parse::<ContentMode>(), Ok(ContentMode::Random));
+        assert_eq!(
+            "csv".parse::<ContentMode>(),
+            Ok(ContentMode::Csv {
+                columns: DEFAULT_CSV_COLUMNS,
+                field_len: DEFAULT_CSV_FIELD_LEN
+            })
+        );
+        assert_eq!(
+            "CSV:3:5".parse::<ContentMode>(),
+            Ok(ContentMode::Csv {
+                columns: 3,
+                field_len: 5
+            })
+        );
+        assert!("csv:0:5".parse::<ContentMode>().is_err());
+        assert!("csv:3:x".parse::<ContentMode>().is_err());
+        assert!("csv:3:5:1".parse::<ContentMode>().is_err());
+        assert!("json".parse::<ContentMode>().is_err());
+        assert_eq!(
+            "dict:100:4".parse::<ContentMode>(),
+            Ok(ContentMode::Dictionary {
+                num_values: 100,
+                value_len: 4
+            })
+        );
+        assert!("dict:0".parse::<ContentMode>().is_err());
+        assert_eq!(
+            "pseudo".parse::<ContentMode>(),
+            Ok(ContentMode::PseudoCompressible {
+                period: DEFAULT_PSEUDO_PERIOD
+            })
+        );
+        assert_eq!(
+            "pseudo:4096".parse::<ContentMode>(),
+            Ok(ContentMode::PseudoCompressible { period: 4096 })
+        );
+        assert!("pseudo:0".parse::<ContentMode>().is_err());
+        assert_eq!(
+            "pseudo:gzip".parse::<ContentMode>(),
+            Ok(ContentMode::PseudoCompressible { period: 16 * 1024 })
+        );
+        assert_eq!(
+            "Pseudo:ZSTD".parse::<ContentMode>(),
+            Ok(ContentMode::from(CompressTarget::Zstd))
+        );
+        assert!("pseudo:brotli".parse::<ContentMode>().is_err());
+        assert!("pseudo:lz4:1".parse::<ContentMode>().is_err());
+    }
+
+    #[test]
+    fn test_compress_target_periods() {
+        // Every target's period fits its own window; Zstd's deliberately
+        // exceeds the DEFLATE and LZ4 windows
+        const DEFLATE_WINDOW: usize = 32 * 1024;
+        const LZ4_DISTANCE: usize = 65_535;
+        let period = |t: CompressTarget| t.repeat_period();
+        assert!(period(CompressTarget::Deflate) < DEFLATE_WINDOW);
+        assert!(period(CompressTarget::Generic) < DEFLATE_WINDOW);
+        assert!(period(CompressTarget::Lz4) < LZ4_DISTANCE);
+        assert!(period(CompressTarget::Zstd) > LZ4_DISTANCE);
+        assert!(period(CompressTarget::Zstd) < 512 * 1024);
+    }
+
+    #[test]
+    fn test_csv_layout_is_position_based() {
+        let content = ContentMode::Csv {
+            columns: 3,
+            field_len: 4,
+        };
+        let raw: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+
+        let fill = ContentFill::new(content, 0);
+        let mut whole = raw.clone();
+        fill.apply(&mut whole, 0, false);
+        for row in whole.split(|&b| b == b'\n').filter(|r| !r.is_empty()) {
+            let fields: Vec<_> = row.split(|&b| b == b',').collect();
+            assert!(fields.iter().all(|f| f.len() <= 4));
+            assert!(row.iter().all(|b| b.is_ascii_graphic()));
+        }
+        assert_eq!(&whole[..15], b"ABCD,FGHI,KLMN\n");
+
+        // Transforming a sub-range at its offset gives the same bytes
+        let mut part = raw[1234..5678].to_vec();
+        fill.apply(&mut part, 1234, false);
+        assert_eq!(&part[..], &whole[1234..5678]);
+    }
+
+    #[test]
+    fn test_dictionary_values() {
+        let content = ContentMode::Dictionary {
+            num_values: 10,
+            value_len: 8,
+        };
+        let fill = ContentFill::new(content, 99);
+        let mut whole = vec![0u8; 8 * 1000];
+        fill.apply(&mut whole, 0, false);
+
+        // Every slot holds one of the 10 values, and several distinct ones appear
+        let mut values: Vec<&[u8]> = whole.chunks_exact(8).collect();
+        values.sort();
+        values.dedup();
+        assert!(values.len() > 1 && values.len() <= 10);
+        assert!(whole.iter().all(|b| b.is_ascii_graphic()));
+
+        // Position-based: an unaligned sub-range matches, and the table follows the seed
+        let mut part = vec![0u8; 1001];
+        fill.apply(&mut part, 3333, true);
+        assert_eq!(&part[..], &whole[3333..4334]);
+
+        let mut other = vec![0u8; 8 * 1000];
+        ContentFill::new(content, 100).apply(&mut other, 0, false);
+        assert_ne!(whole, other);
+    }
+
+    #[test]
+    fn test_per_block_layout() {
+        let csv = ContentMode::Csv {
+            columns: 3,
+            field_len: 4,
+        };
+        let dict = ContentMode::Dictionary {
+            num_values: 50,
+            value_len: 7,
+        };
+        let raw: Vec<u8> = (0..=255u8).cycle().take(4 * 1000).collect();
+        for content in [csv, dict] {
+            // Four 1000-byte blocks, blocks 2 and 3 copies of blocks 0 and 1
+            let fill = ContentFill::new(content, 5).per_block(1000, 2);
+            let mut whole = raw.clone();
+            fill.apply(&mut whole, 0, false);
+            let mut part = raw[990..2345].to_vec();
+            fill.apply(&mut part, 990, true);
+            assert_eq!(&part[..], &whole[990..2345]);
+
+            let raw_copy = [&raw[..2000], &raw[..2000]].concat();
+            let mut copies = raw_copy.clone();
+            fill.apply(&mut copies, 0, false);
+            assert_eq!(&copies[..2000], &copies[2000..]);
+            assert_ne!(&copies[..1000], &copies[1000..2000]);
+            if content == csv {
+                // Every block starts a row and ends one
+                assert!(whole.chunks(1000).all(|b| b[999] == b'\n'));
+                let separators = |b: &[u8]| {
+                    b.iter()
+                        .map(|&c| c == b',' || c == b'\n')
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(separators(&whole[1000..2000]), separators(&whole[..1000]));
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// src/cpus.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! CPU limits of the current process, for sizing the default thread pool
+//!
+//! In containers and on CI runners the host may have dozens of cores while the
+//! process is confined to one or two, by a CPU affinity mask (`taskset`, cpusets)
+//! or a cgroup CPU quota (`docker --cpus`, Kubernetes limits). A pool sized to the
+//! host oversubscribes the quota badly: threads are throttled in turn and
+//! throughput collapses. When `max_threads` is None, pools are sized to
+//! `effective_cpu_count()`, the tightest of these limits.
+
+use std::sync::OnceLock;
+
+/// Host/limit ratio at which the cap is logged as a warning instead of at debug level
+const LIMITED_WARN_RATIO: usize = 2;
+
+static EFFECTIVE_CPUS: OnceLock<usize> = OnceLock::new();
+
+/// Number of CPUs this process can actually use (at least 1)
+///
+/// The minimum of the CPU affinity mask, the cgroup CPU quota (v1 or v2, rounded
+/// up to whole CPUs) and `num_cpus::get()`. Detected once per process; the first
+/// call logs a warning when this is well below the host's online CPU count.
+pub fn effective_cpu_count() -> usize {
+    *EFFECTIVE_CPUS.get_or_init(|| {
+        let affinity = affinity_cpu_count();
+        let quota = cgroup_cpu_limit();
+        let effective = [Some(num_cpus::get()), affinity, quota]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(1)
+            .max(1);
+
+        let host = host_cpu_count();
+        if host >= effective * LIMITED_WARN_RATIO {
+            tracing::warn!(
+                "Process is limited to {} of {} host CPUs (affinity: {:?}, cgroup quota: {:?}); \
+                 capping the default thread pool at {} threads. Set max_threads to override.",
+                effective,
+                host,
+                affinity,
+                quota,
+                effective
+            );
+        } else {
+            tracing::debug!(
+                "Effective CPUs: {} (host {}, affinity {:?}, cgroup quota {:?})",
+                effective,
+                host,
+                affinity,
+                quota
+            );
+        }
+        effective
+    })
+}
+
+/// CPUs in the process affinity mask, if it can be determined
+pub(crate) fn affinity_cpu_count() -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let cpus = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))?
+            .trim();
+        let count = parse_cpu_list(cpus);
+        if count > 0 {
+            tracing::debug!("CPU affinity mask: {} CPUs ({})", count, cpus);
+            return Some(count);
+        }
+    }
+    None
+}
+
+/// CPUs online on the host, ignoring affinity and quotas
+fn host_cpu_count() -> usize {
+    #[cfg(target_os = "linux")]
+    if let Ok(online) = std::fs::read_to_string("/sys/devices/system/cpu/online") {
+        let count = parse_cpu_list(online.trim());
+        if count > 0 {
+            return count;
+        }
+    }
+    num_cpus::get()
+}
+
+/// Parse Linux CPU list (e.g., "0-23" or "0-11,24-35")
+///
+/// Malformed entries, including reversed ranges like "3-1", are skipped rather
+/// than counted, so a garbled list yields 0 and callers fall back to other sources.
+pub(crate) fn parse_cpu_list(cpu_list: &str) -> usize {
+    let mut count: usize = 0;
+    for range in cpu_list.split(',') {
+        let range = range.trim();
+        if range.is_empty() {
+            continue;
+        }
+
+        let cpus = match range.split_once('-') {
+            Some((start, end)) => {
+                match (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    (Ok(s), Ok(e)) if e >= s => (e - s).saturating_add(1),
+                    _ => {
+                        tracing::debug!("Skipping malformed CPU range {:?}", range);
+                        0
+                    }
+                }
+            }
+            None => range.parse::<usize>().is_ok() as usize,
+        };
+        count = count.saturating_add(cpus);
+    }
+    count
+}
+
+/// CPU limit from the process's cgroup quota, in whole CPUs (None = unlimited)
+#[cfg(target_os = "linux")]
+fn cgroup_cpu_limit() -> Option<usize> {
+    let cgroups = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let mut limit: Option<usize> = None;
+    let mut tighten = |cpus: Option<usize>| {
+        if let Some(cpus) = cpus {
+            limit = Some(limit.map_or(cpus, |l| l.min(cpus)));
+        }
+    };
+
+    for line in cgroups.lines() {
+        // "hierarchy-ID:controller-list:cgroup-path"
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        if id == "0" && controllers.is_empty() {
+            // cgroup v2: every ancestor's cpu.max applies
+            for dir in cgroup_ancestors("/sys/fs/cgroup", path) {
+                if let Ok(max) = std::fs::read_to_string(format!("{}/cpu.max", dir)) {
+                    tighten(parse_cpu_max(&max));
+                }
+            }
+        } else if controllers.split(',').any(|c| c == "cpu") {
+            // cgroup v1: the cpu controller is mounted at cpu or cpu,cpuacct
+            for mount in ["/sys/fs/cgroup/cpu", "/sys/fs/cgroup/cpu,cpuacct"] {
+                for dir in cgroup_ancestors(mount, path) {
+                    let read = |file: &str| {
+                        std::fs::read_to_string(format!("{}/{}", dir, file))
+                            .ok()
+                            .and_then(|v| v.trim().parse::<i64>().ok())
+                    };
+                    if let (Some(quota), Some(period)) =
+                        (read("cpu.cfs_quota_us"), read("cpu.cfs_period_us"))
+                    {
+                        tighten(quota_cpus(quota, period));
+                    }
+                }
+            }
+        }
+    }
+    limit
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cgroup_cpu_limit() -> Option<usize> {
+    None
+}
+
+/// `mount/path` and each of its parent directories down to `mount`
+///
+/// Inside a container the cgroup namespace root is the container's own cgroup, so
+/// the listed path may not exist below the mount; the mount root is always included.
+#[cfg(target_os = "linux")]
+fn cgroup_ancestors(mount: &str, path: &str) -> Vec<String> {
+    let mut dirs = vec![mount.to_string()];
+    let mut current = mount.to_string();
+    for component in path.split('/').filter(|c| !c.is_empty()) {
+        current = format!("{}/{}", current, component);
+        dirs.push(current.clone());
+    }
+    dirs
+}
+
+/// Parse cgroup v2 `cpu.max` ("max 100000" or "QUOTA PERIOD") into whole CPUs
+fn parse_cpu_max(contents: &str) -> Option<usize> {
+    let mut fields = contents.split_whitespace();
+    let quota = fields.next()?;
+    if quota == "max" {
+        return None;
+    }
+    let period = fields.next().unwrap_or("100000");
+    quota_cpus(quota.parse().ok()?, period.parse().ok()?)
+}
+
+/// Whole CPUs granted by a CFS quota per period (None for no quota)
+fn quota_cpus(quota: i64, period: i64) -> Option<usize> {
+    if quota <= 0 || period <= 0 {
+        return None;
+    }
+    Some((quota as u64).div_ceil(period as u64).max(1) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_limit_parsing() {
+        assert_eq!(parse_cpu_list("0-23"), 24);
+        assert_eq!(parse_cpu_list("0-11,24-35"), 24);
+        assert_eq!(parse_cpu_list("3"), 1);
+        assert_eq!(parse_cpu_list(""), 0);
+        assert_eq!(parse_cpu_list("3-1"), 0);
+        assert_eq!(parse_cpu_list("0-3,7-5,9"), 5);
+        assert_eq!(parse_cpu_list("0-3,"), 4);
+        assert_eq!(parse_cpu_list(",,1,"), 1);
+        assert_eq!(parse_cpu_list("0-,-4,a-b,x"), 0);
+        assert_eq!(parse_cpu_list(&format!("0-{}", usize::MAX)), usize::MAX);
+
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max("200000 100000\n"), Some(2));
+        assert_eq!(parse_cpu_max("150000 100000"), Some(2));
+        assert_eq!(parse_cpu_max("50000 100000"), Some(1));
+
+        assert_eq!(quota_cpus(-1, 100000), None);
+        assert_eq!(quota_cpus(400000, 100000), Some(4));
+
+        let effective = effective_cpu_count();
+        assert!(effective >= 1 && effective <= num_cpus::get());
+    }
+}
@@ -0,0 +1,111 @@
+// src/feature_matrix.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! API checks compiled under every feature combination
+//!
+//! `DataBuffer` and the generation paths are `cfg`-heavy: each `numa` /
+//! `thread-pinning` / `parallel` combination builds a different set of variants and
+//! code paths, so a method missing from one of them only shows up in that build.
+//! These tests touch the public API the same way in every build and check that the
+//! bytes do not depend on which paths were compiled in. CI runs them for each
+//! combination in the `features` job.
+
+use crate::constants::BLOCK_SIZE;
+use crate::generator::DataBuffer;
+use crate::{generate_data, generate_range, DataGenerator, GeneratorConfig, NumaMode};
+
+fn config() -> GeneratorConfig {
+    GeneratorConfig {
+        size: BLOCK_SIZE * 5 + 321,
+        dedup_factor: 2,
+        compress_factor: 3,
+        block_size: Some(BLOCK_SIZE),
+        seed: Some(0xfea7),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_data_buffer_api() {
+    let mut data = generate_data(config());
+    assert_eq!(data.len(), config().size);
+    assert!(!data.is_empty());
+    assert!(!data.as_ptr().is_null());
+    assert!(!data.as_mut_ptr().is_null());
+    assert_eq!(data.numa_node(), None);
+    data.as_mut_slice()[0] ^= 0xff;
+    data.truncate(10);
+    assert_eq!(data.as_slice().len(), 10);
+    assert_eq!(data.into_bytes().len(), 10);
+
+    for empty in [DataBuffer::alloc_uma(0), DataBuffer::alloc_aligned(0, 4096)] {
+        assert!(empty.is_empty());
+    }
+    let aligned = DataBuffer::alloc_aligned(8192, 4096);
+    assert_eq!(aligned.as_ptr() as usize % 4096, 0);
+    assert_eq!(aligned.into_bytes().len(), 8192);
+}
+
+#[test]
+fn test_output_independent_of_compiled_paths() {
+    let expected = generate_data(config());
+
+    // Settings that select NUMA, pinning and threading paths never change the bytes
+    let variants = [
+        GeneratorConfig {
+            numa_mode: NumaMode::Disabled,
+            ..config()
+        },
+        GeneratorConfig {
+            numa_mode: NumaMode::Force,
+            max_threads: Some(3),
+            ..config()
+        },
+        GeneratorConfig {
+            max_threads: Some(1),
+            ..config()
+        },
+        GeneratorConfig {
+            cpu_affinity: Some(vec![0]),
+            ..config()
+        },
+        GeneratorConfig {
+            use_global_pool: true,
+            ..config()
+        },
+    ];
+    for variant in variants {
+        assert_eq!(generate_data(variant).as_slice(), expected.as_slice());
+    }
+
+    let mut range = vec![0u8; BLOCK_SIZE + 77];
+    generate_range(&config(), BLOCK_SIZE as u64 - 7, &mut range);
+    assert_eq!(
+        &expected.as_slice()[BLOCK_SIZE - 7..][..range.len()],
+        &range[..]
+    );
+}
+
+#[test]
+fn test_data_generator_api() {
+    let expected = generate_data(config());
+    let mut gen = DataGenerator::new(config());
+    assert_eq!(gen.total_size(), expected.len());
+
+    let mut streamed = vec![0u8; gen.total_size()];
+    for chunk in streamed.chunks_mut(BLOCK_SIZE * 2 + 1) {
+        assert_eq!(gen.fill_chunk(chunk), chunk.len());
+    }
+    assert!(gen.is_complete());
+    assert_eq!(&streamed[..], expected.as_slice());
+
+    let stats = gen.finish();
+    assert_eq!(stats.bytes_generated, expected.len() as u64);
+    assert_eq!(stats.numa_compiled, cfg!(feature = "numa"));
+
+    gen.reset();
+    let mut head = vec![0u8; 100];
+    gen.fill_chunk(&mut head);
+    assert_eq!(&head[..], &expected.as_slice()[..100]);
+}
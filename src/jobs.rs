@@ -0,0 +1,214 @@
+// src/jobs.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Per-block generation work for custom executors
+//!
+//! `DataGenerator::fill_chunk()` fills the blocks of a chunk on its rayon pool.
+//! Embedders running their own executor can instead split a chunk into
+//! [`BlockJob`]s with `DataGenerator::block_jobs()` and run each one with
+//! [`execute_block`] against the generator's [`BlockLayout`], on any thread and
+//! in any order. The bytes are identical to what `fill_chunk()` would have
+//! written; no rayon pool is involved.
+
+use crate::block::{fill_block_at, scaled_copy_len, CompressibleFill};
+use crate::content::ContentFill;
+use crate::generator::GeneratorConfig;
+
+/// One block's share of a chunk: the bytes of block `block_index` from
+/// `block_offset` on, as many as `into` holds
+///
+/// Jobs from one `block_jobs()` call cover disjoint parts of the chunk, so they
+/// can run concurrently.
+#[derive(Debug)]
+pub struct BlockJob<'a> {
+    /// Index of the block in the stream
+    pub block_index: usize,
+    /// Offset of `into[0]` within the block (non-zero only for a chunk's first job)
+    pub block_offset: usize,
+    /// Destination for the bytes
+    pub into: &'a mut [u8],
+}
+
+/// Everything a [`BlockJob`] needs from its generator: seed, block layout and
+/// content mode
+///
+/// Borrowed from the generator by `DataGenerator::block_layout()`; it is `Sync`,
+/// so one layout can be shared by every worker of a chunk.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockLayout<'a> {
+    pub(crate) config: &'a GeneratorConfig,
+    pub(crate) content: &'a ContentFill,
+    pub(crate) copy_lens: &'a [usize],
+    pub(crate) block_size: usize,
+    pub(crate) unique_blocks: usize,
+    pub(crate) sequence_origin: usize,
+    pub(crate) seed: u64,
+    pub(crate) fill: CompressibleFill,
+    pub(crate) final_block: (usize, usize), // (index, generated length)
+}
+
+impl BlockLayout<'_> {
+    /// Block size of the stream
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Generate block `block_idx` into `out`, a whole block's worth of bytes
+    ///
+    /// The final block is generated at its own length, leaving the rest of `out`
+    /// untouched. Prefixes and content are applied separately by `finish()`.
+    pub(crate) fn fill_block(&self, block_idx: usize, out: &mut [u8]) {
+        let ub = block_idx % self.unique_blocks;
+        let full_len = out.len();
+        let len = if block_idx == self.final_block.0 {
+            self.final_block.1
+        } else {
+            full_len
+        };
+        fill_block_at(
+            &mut out[..len],
+            ub,
+            scaled_copy_len(self.copy_lens[ub], len, full_len),
+            self.config
+                .keystream_sequence(block_idx, self.sequence_origin, self.unique_blocks),
+            self.seed,
+            self.fill,
+            (block_idx * full_len) as u64,
+            self.config.dictionary(),
+        );
+    }
+
+    /// Stamp unique prefixes and apply the content mode to stream bytes from `offset`
+    pub(crate) fn finish(&self, buf: &mut [u8], offset: u64, parallel: bool) {
+        self.config
+            .stamp_unique_prefixes(buf, offset, self.block_size, self.seed, parallel);
+        self.content.apply(buf, offset, parallel);
+    }
+}
+
+/// Run `job` on the calling thread
+///
+/// A job covering a whole block is generated in place; a partial block is
+/// generated whole into a temporary buffer and the covered bytes copied, as
+/// `fill_chunk()` does.
+pub fn execute_block(job: BlockJob<'_>, layout: &BlockLayout<'_>) {
+    let BlockJob {
+        block_index,
+        block_offset,
+        into,
+    } = job;
+    let block_size = layout.block_size;
+    if block_offset == 0 && into.len() == block_size {
+        layout.fill_block(block_index, into);
+    } else {
+        let mut block = vec![0u8; block_size];
+        layout.fill_block(block_index, &mut block);
+        into.copy_from_slice(&block[block_offset..block_offset + into.len()]);
+    }
+    layout.finish(
+        into,
+        (block_index * block_size + block_offset) as u64,
+        false,
+    );
+}
+
+/// Iterator over the [`BlockJob`]s of one chunk, in stream order
+///
+/// Returned by `DataGenerator::block_jobs()`.
+#[derive(Debug)]
+pub struct BlockJobs<'a> {
+    rest: &'a mut [u8],
+    block_index: usize,
+    block_offset: usize,
+    block_size: usize,
+}
+
+impl<'a> BlockJobs<'a> {
+    /// Jobs filling `buf` with the stream bytes from `position`
+    pub(crate) fn new(buf: &'a mut [u8], position: usize, block_size: usize) -> Self {
+        Self {
+            rest: buf,
+            block_index: position / block_size,
+            block_offset: position % block_size,
+            block_size,
+        }
+    }
+}
+
+impl<'a> Iterator for BlockJobs<'a> {
+    type Item = BlockJob<'a>;
+
+    fn next(&mut self) -> Option<BlockJob<'a>> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = (self.block_size - self.block_offset).min(self.rest.len());
+        let (into, rest) = std::mem::take(&mut self.rest).split_at_mut(len);
+        self.rest = rest;
+        let job = BlockJob {
+            block_index: self.block_index,
+            block_offset: self.block_offset,
+            into,
+        };
+        self.block_index += 1;
+        self.block_offset = 0;
+        Some(job)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.block_offset + self.rest.len()).div_ceil(self.block_size);
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for BlockJobs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLOCK_SIZE;
+    use crate::content::ContentMode;
+    use crate::generator::DataGenerator;
+
+    #[test]
+    fn test_block_jobs_match_fill_chunk() {
+        let config = GeneratorConfig {
+            size: BLOCK_SIZE * 6 + 777,
+            dedup_factor: 2,
+            compress_factor: 3,
+            unique_prefix_bytes: 100,
+            content: ContentMode::Csv {
+                columns: 8,
+                field_len: 12,
+            },
+            block_size: Some(BLOCK_SIZE),
+            max_threads: Some(2),
+            seed: Some(1184),
+            ..Default::default()
+        };
+        let mut expected = vec![0u8; config.size];
+        DataGenerator::new(config.clone()).fill_chunk(&mut expected);
+
+        let mut gen = DataGenerator::new(config);
+        let mut output = vec![0u8; expected.len()];
+        let (head, rest) = output.split_at_mut(BLOCK_SIZE / 2 + 5);
+        gen.fill_chunk(head);
+
+        // The rest of the stream as jobs, run on scoped threads in reverse order
+        let jobs: Vec<BlockJob> = gen.block_jobs(rest).collect();
+        assert_eq!(jobs.len(), 7);
+        assert_eq!(
+            (jobs[0].block_index, jobs[0].block_offset),
+            (0, BLOCK_SIZE / 2 + 5)
+        );
+        assert!(gen.is_complete());
+        let layout = gen.block_layout();
+        std::thread::scope(|s| {
+            for job in jobs.into_iter().rev() {
+                s.spawn(move || execute_block(job, &layout));
+            }
+        });
+        assert!(output == expected);
+    }
+}
@@ -0,0 +1,119 @@
+// src/keystream.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! The Xoshiro256++ keystream behind every generated block, as a standalone generator
+//!
+//! Incompressible bytes in a dataset are this keystream seeded with
+//! `seed_base + block_sequence`, the sequence being the unique block index. `KeyStream` exposes the same stream for uses
+//! that have nothing to do with dedup or compression, such as filling test
+//! buffers or producing fuzzing inputs.
+
+use rand_xoshiro::rand_core::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Fast, seedable stream of pseudo-random bytes
+///
+/// Not cryptographically secure. The seed is expanded with SplitMix64
+/// (`seed_from_u64`) and words are emitted little-endian, so a given seed
+/// produces the same bytes on every platform.
+///
+/// # Example
+/// ```rust
+/// use dgen_data::KeyStream;
+///
+/// let mut a = vec![0u8; 4096];
+/// let mut b = vec![0u8; 4096];
+/// KeyStream::new(42).fill(&mut a);
+/// KeyStream::new(42).fill(&mut b);
+/// assert_eq!(a, b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyStream {
+    rng: Xoshiro256PlusPlus,
+}
+
+impl KeyStream {
+    /// Create a stream from a 64-bit seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Xoshiro256PlusPlus::seed_from_u64(seed),
+        }
+    }
+
+    /// Create a stream from fresh entropy (time + OS RNG), different on every call
+    #[cfg(feature = "generator")]
+    pub fn from_entropy() -> Self {
+        Self::new(crate::generator::generate_call_entropy())
+    }
+
+    /// Stream used for block `block_sequence` of a dataset seeded with `seed_base`
+    pub(crate) fn for_block(seed_base: u64, block_sequence: u64) -> Self {
+        Self::new(seed_base.wrapping_add(block_sequence))
+    }
+
+    /// Fill `buf` with the next `buf.len()` bytes of the stream
+    ///
+    /// Bytes are drawn 8 at a time, and a trailing 1-4 bytes come from a 32-bit
+    /// draw, so splitting one fill into several gives the same bytes only when
+    /// every piece but the last is a multiple of 8 bytes long.
+    #[inline]
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        self.rng.fill_bytes(buf);
+    }
+
+    /// Next 64 bits of the stream
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generator")]
+    fn test_keystream_matches_block_fill() {
+        use crate::generator::{generate_data, GeneratorConfig};
+
+        // An incompressible, non-dedup dataset is the keystream of each block in turn
+        let block_size = 1024 * 1024;
+        let config = GeneratorConfig {
+            size: 4 * block_size,
+            block_size: Some(block_size),
+            seed: Some(9),
+            max_threads: Some(1),
+            ..Default::default()
+        };
+        let data = generate_data(config);
+        for (i, block) in data.as_slice().chunks(block_size).enumerate() {
+            let mut expected = vec![0u8; block_size];
+            KeyStream::for_block(9, i as u64).fill(&mut expected);
+            assert_eq!(block, &expected[..], "block {}", i);
+        }
+    }
+
+    #[test]
+    fn test_keystream_continues_across_fills() {
+        let mut whole = [0u8; 100];
+        KeyStream::new(5).fill(&mut whole);
+
+        let mut ks = KeyStream::new(5);
+        let mut parts = [0u8; 100];
+        ks.fill(&mut parts[..64]);
+        ks.fill(&mut parts[64..]);
+        assert_eq!(whole, parts);
+    }
+
+    #[test]
+    #[cfg(feature = "generator")]
+    fn test_keystream_from_entropy() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        KeyStream::from_entropy().fill(&mut a);
+        KeyStream::from_entropy().fill(&mut b);
+        assert_ne!(a, b);
+    }
+}
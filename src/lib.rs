@@ -10,25 +10,158 @@
 //! - Controllable compression ratios (1:1 to N:1)
 //! - NUMA-aware parallel generation (optional)
 //! - Zero-copy Python bindings via PyO3
+//!
+//! # Allocator
+//!
+//! The `jemalloc` and `mimalloc` features replace the global allocator for every
+//! binary (and the Python extension) that links this crate. The system allocator is
+//! fine for the streaming path, which reuses one buffer, but one-shot
+//! [`generate_data`] allocates and frees a full-size buffer per call: with glibc each
+//! large buffer is a fresh `mmap`/`munmap` plus page faults, while jemalloc/mimalloc
+//! retain and reuse the pages. On musl the system allocator is also much slower under
+//! concurrent allocation. The tradeoff is a C build dependency, longer builds, and
+//! higher resident memory since freed pages are kept around rather than returned to
+//! the OS immediately. Buffers allocated by Python (e.g. `create_bytearrays`) are not
+//! affected. If both are enabled (e.g. `--all-features`), jemalloc is used.
+//!
+//! # Features
+//!
+//! `parallel` (default) provides the full generator: rayon thread pools,
+//! [`generate_data`], [`DataGenerator`] and everything built on them. Every other
+//! feature implies it. For single-threaded embedding, `generator` alone keeps the
+//! same API but drops rayon: every fill loop runs sequentially on the calling
+//! thread, no thread pool is built and `max_threads` is ignored. The output is
+//! identical to the parallel build. For the bare block-fill logic without a
+//! thread pool, hwloc or PyO3, build with `default-features = false, features =
+//! ["core"]`: only [`block`], [`keystream`] and [`constants`] are compiled, and
+//! the caller drives parallelism.
+//!
+//! `serde` derives `Serialize`/`Deserialize` for [`GeneratorConfig`],
+//! [`GenerationStats`] and [`GenerationReceipt`], so receipts can be archived as JSON.
+
+#[cfg(not(any(feature = "generator", feature = "core")))]
+compile_error!(
+    "enable feature `parallel` (the full generator), `generator` (sequential generator) or `core` (block-fill core only)"
+);
+
+extern crate alloc;
 
-// Core modules
+// jemalloc takes precedence when both are enabled
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+#[cfg(all(feature = "mimalloc", not(feature = "jemalloc")))]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+// Block-fill core (no thread pool)
+pub mod block;
 pub mod constants;
+pub mod keystream;
+
+// Generator (parallel with `parallel`, sequential otherwise)
+#[cfg(feature = "generator")]
+pub mod aligned;
+#[cfg(feature = "generator")]
+pub mod benchmark;
+#[cfg(feature = "generator")]
+pub mod content;
+#[cfg(feature = "generator")]
+pub mod cpus;
+#[cfg(feature = "generator")]
 pub mod generator;
+#[cfg(feature = "generator")]
+pub mod jobs;
+#[cfg(feature = "generator")]
+pub mod memory;
+#[cfg(feature = "generator")]
+pub mod pool;
+#[cfg(feature = "generator")]
+mod rate_limit;
+#[cfg(feature = "generator")]
+pub mod receipt;
+#[cfg(feature = "generator")]
+pub mod ring;
+#[cfg(all(feature = "generator", not(feature = "parallel")))]
+mod sequential;
+
+#[cfg(all(test, feature = "generator"))]
+mod feature_matrix;
 
 #[cfg(feature = "numa")]
 pub mod numa;
 
+#[cfg(feature = "phase-timing")]
+pub mod phase_timing;
+
+#[cfg(feature = "block-timing")]
+pub mod block_timing;
+
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub mod shm;
+
+#[cfg(all(feature = "mmap", unix))]
+pub mod mmap_file;
+
+#[cfg(feature = "tokio")]
+pub mod tokio_channel;
+
 // Python bindings
 #[cfg(feature = "python-bindings")]
 mod python_api;
 
 // Re-export main API
+pub use block::CompressibleFill;
+pub use constants::DATA_FORMAT_VERSION;
+pub use keystream::KeyStream;
+
+#[cfg(feature = "generator")]
+pub use aligned::AlignedBuffer;
+#[cfg(feature = "generator")]
+pub use benchmark::{benchmark, benchmark_streaming, benchmark_with_warmup, BenchmarkResult};
+#[cfg(feature = "generator")]
+pub use content::{CompressTarget, ContentMode};
+#[cfg(feature = "generator")]
+pub use cpus::effective_cpu_count;
+#[cfg(feature = "generator")]
 pub use generator::{
-    generate_data, generate_data_simple, DataGenerator, GeneratorConfig, NumaMode,
+    fill_buffers, generate_buffers, generate_data, generate_data_simple, generate_range,
+    generate_to_files, keyed_seed, try_generate_data, verify_range, ConfigError, DataGenerator,
+    EffectiveConfig, GenerationStats, GeneratorConfig, NumaBinding, NumaMode, VerifyError,
 };
+#[cfg(feature = "generator")]
+pub use jobs::{execute_block, BlockJob, BlockJobs, BlockLayout};
+#[cfg(feature = "generator")]
+pub use memory::available_memory;
+
+#[cfg(feature = "generator")]
+pub use pool::{GeneratorPool, StreamHandle};
+#[cfg(feature = "generator")]
+pub use receipt::{generate_with_receipt, regenerate, GenerationReceipt};
+#[cfg(feature = "generator")]
+pub use ring::{RingBuffer, RingGenerator};
 
 #[cfg(feature = "numa")]
-pub use numa::{NumaNode, NumaTopology};
+pub use numa::{NumaAllocation, NumaNode, NumaTopology};
+
+#[cfg(feature = "block-timing")]
+pub use block_timing::{generate_data_with_timing, BlockTimings};
+
+#[cfg(feature = "phase-timing")]
+pub use phase_timing::{phase_timings, reset_phase_timings, PhaseTimings};
+
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub use shm::{generate_to_shm, ShmHandle};
+
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap_file::generate_to_mmap;
+
+#[cfg(feature = "tokio")]
+pub use tokio_channel::spawn_generator;
+
+#[cfg(feature = "python-bindings")]
+pub use python_api::PyBytesView;
 
 // PyO3 module initialization
 #[cfg(feature = "python-bindings")]
@@ -0,0 +1,58 @@
+// src/memory.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Memory available for one-shot generation
+//!
+//! `generate_data()` allocates the whole dataset at once, so a size beyond free
+//! memory sends the host into swap or the process to the OOM killer long before
+//! any data is produced. `available_memory()` reports what the kernel estimates
+//! can be allocated without swapping, and `GeneratorConfig::fits_in_memory()`
+//! compares it with `estimated_peak_bytes()`.
+
+/// Bytes the kernel estimates can be allocated without swapping
+///
+/// `MemAvailable` from `/proc/meminfo` on Linux, read on every call since it
+/// changes constantly. None on other platforms or when it cannot be read.
+pub fn available_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let available = parse_mem_available(&meminfo);
+        tracing::debug!("MemAvailable: {:?} bytes", available);
+        available
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// `MemAvailable` of a `/proc/meminfo` listing, in bytes
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    kib.checked_mul(1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mem_available() {
+        let meminfo = "MemTotal:       65831548 kB\n\
+                       MemFree:         1234567 kB\n\
+                       MemAvailable:   40000000 kB\n\
+                       Buffers:          123456 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(40_000_000 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+        assert_eq!(parse_mem_available("MemAvailable: lots\n"), None);
+
+        #[cfg(target_os = "linux")]
+        assert!(available_memory().is_some_and(|bytes| bytes > 0));
+    }
+}
@@ -0,0 +1,159 @@
+// src/mmap_file.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Generate a file in place through a writable memory mapping
+//!
+//! The file is sized up front, mapped shared and filled chunk by chunk with
+//! `generate_range()`, so no user-space buffer is allocated or copied. Written
+//! pages stay mapped into the process by default, which makes the resident set
+//! grow to the file size on a huge file; `release_written` flushes each chunk
+//! and drops it from the mapping (`MADV_DONTNEED`) so the page cache can reclaim
+//! it and the resident set stays around one chunk.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+
+use memmap2::{MmapMut, UncheckedAdvice};
+
+use crate::cpus::effective_cpu_count;
+use crate::generator::{generate_range, thread_pool_builder, DataGenerator, GeneratorConfig};
+
+/// Write `config.aligned_size()` generated bytes to `path` through a memory mapping
+///
+/// The file is created or truncated, sized, and filled in `chunk_size` pieces (0
+/// selects `DataGenerator::recommended_chunk_size()`) on a rayon pool of
+/// `config.max_threads` threads. With `config.seed` set the file matches
+/// `generate_data(config.clone())` byte-for-byte; without one a single seed is
+/// picked for the whole file.
+///
+/// With `release_written`, each chunk is synced to disk (`msync`) and then
+/// advised `MADV_DONTNEED` before the next one is generated. Syncing first makes
+/// the pages clean, so the kernel can drop them instead of holding gigabytes of
+/// dirty page cache, at the cost of waiting for the disk once per chunk. Use a
+/// chunk size that is a multiple of the page size so whole pages are released.
+///
+/// # Returns
+/// Number of bytes written
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{generate_data, generate_to_mmap, GeneratorConfig};
+///
+/// let config = GeneratorConfig {
+///     size: 4 * 1024 * 1024,
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+/// let path = std::env::temp_dir().join(format!("dgen-doc-{}.bin", std::process::id()));
+/// assert_eq!(generate_to_mmap(&path, &config, 1024 * 1024, true).unwrap(), 4 * 1024 * 1024);
+/// assert_eq!(std::fs::read(&path).unwrap(), generate_data(config).as_slice());
+/// std::fs::remove_file(path).unwrap();
+/// ```
+pub fn generate_to_mmap(
+    path: &Path,
+    config: &GeneratorConfig,
+    chunk_size: usize,
+    release_written: bool,
+) -> io::Result<u64> {
+    let chunk_size = if chunk_size == 0 {
+        DataGenerator::recommended_chunk_size()
+    } else {
+        chunk_size
+    };
+    // Chunks are generated by separate calls, which must share one seed
+    let config = GeneratorConfig {
+        seed: Some(config.call_seed()),
+        ..config.clone()
+    };
+    let size = config.aligned_size();
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    file.set_len(size as u64)?;
+    if size == 0 {
+        return Ok(0);
+    }
+
+    // SAFETY: the file was just truncated and sized by this process; like any
+    // file mapping, this assumes no other process resizes it while it is filled
+    let mut map = unsafe { MmapMut::map_mut(&file)? };
+    let num_threads = config.max_threads.unwrap_or_else(effective_cpu_count);
+
+    tracing::info!(
+        "Generating {} bytes into mapped file {}: dedup={}, compress={}, threads={}, release_written={}",
+        size,
+        path.display(),
+        config.dedup_factor,
+        config.compress_factor,
+        num_threads,
+        release_written
+    );
+
+    let pool = thread_pool_builder(
+        num_threads,
+        config.thread_name_prefix.as_deref(),
+        config.thread_stack_size,
+    )
+    .build()
+    .map_err(io::Error::other)?;
+
+    let mut offset = 0;
+    while offset < size {
+        let len = chunk_size.min(size - offset);
+        pool.install(|| generate_range(&config, offset as u64, &mut map[offset..offset + len]));
+        if release_written {
+            map.flush_range(offset, len)?;
+            // SAFETY: the mapping is shared and file-backed, and the range was just
+            // synced, so dropping its pages loses nothing: a later access faults the
+            // bytes back in from the file. No references into the range are live.
+            unsafe { map.unchecked_advise_range(UncheckedAdvice::DontNeed, offset, len)? };
+        }
+        offset += len;
+    }
+    if !release_written {
+        map.flush()?;
+    }
+
+    tracing::debug!("generate_to_mmap: wrote {} bytes", size);
+    Ok(size as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate_data;
+
+    #[test]
+    fn test_generate_to_mmap() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = GeneratorConfig {
+            size: 5 * 1024 * 1024 + 123,
+            dedup_factor: 2,
+            compress_factor: 3,
+            seed: Some(5),
+            max_threads: Some(2),
+            ..Default::default()
+        };
+        let expected = generate_data(config.clone());
+
+        for (chunk_size, release_written) in [(0, false), (1024 * 1024, true), (100_000, true)] {
+            let path = dir.path().join(format!("out-{}.bin", chunk_size));
+            // An existing longer file is truncated
+            std::fs::write(&path, vec![0xAB; 6 * 1024 * 1024]).unwrap();
+            let written = generate_to_mmap(&path, &config, chunk_size, release_written).unwrap();
+            assert_eq!(written, expected.len() as u64);
+            assert!(std::fs::read(&path).unwrap() == expected.as_slice());
+        }
+
+        let empty = dir.path().join("empty.bin");
+        let config = GeneratorConfig { size: 0, ..config };
+        assert_eq!(generate_to_mmap(&empty, &config, 0, true).unwrap(), 0);
+        assert_eq!(std::fs::metadata(&empty).unwrap().len(), 0);
+    }
+}
@@ -7,8 +7,12 @@
 //! Uses hwlocality for cross-platform NUMA topology detection
 
 use anyhow::Result;
-use hwlocality::{object::types::ObjectType, Topology};
+use hwlocality::{memory::binding::Bytes, object::types::ObjectType, Topology};
 use std::collections::HashSet;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::sync::{Arc, OnceLock};
+
+use crate::generator::NumaBinding;
 
 /// NUMA node information
 #[derive(Debug, Clone)]
@@ -42,7 +46,14 @@ impl NumaTopology {
         tracing::debug!("Detecting NUMA topology via hwlocality...");
 
         let topology = Topology::new()?;
+        Ok(Self::from_topology(&topology))
+    }
 
+    /// Build from an hwloc topology the caller already holds
+    ///
+    /// Avoids a second topology discovery when the application has loaded one
+    /// (e.g. for its own scheduling); pair with `DataBuffer::alloc_numa_with_topology()`.
+    pub fn from_topology(topology: &Topology) -> Self {
         // Get all NUMA nodes
         let numa_nodes: Vec<_> = topology.objects_with_type(ObjectType::NUMANode).collect();
 
@@ -83,13 +94,13 @@ impl NumaTopology {
         let physical_cores = num_cpus::get_physical();
         let logical_cpus = num_cpus::get();
 
-        Ok(Self {
+        Self {
             num_nodes,
             physical_cores,
             logical_cpus,
             nodes,
             is_uma,
-        })
+        }
     }
 
     /// Check if NUMA-aware optimizations should be enabled
@@ -113,6 +124,202 @@ impl NumaTopology {
             .find(|n| n.node_id == node_id)
             .map(|n| n.cpus.as_slice())
     }
+
+    /// One-line description: node count, cores and per-node CPU counts
+    ///
+    /// e.g. `"2 NUMA nodes, 32 cores / 64 CPUs: node 0 = 32 CPUs, node 1 = 32 CPUs"`
+    pub fn summary(&self) -> String {
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|n| format!("node {} = {} CPUs", n.node_id, n.cpus.len()))
+            .collect();
+        format!(
+            "{} NUMA node{}, {} cores / {} CPUs: {}",
+            self.num_nodes,
+            if self.num_nodes == 1 { "" } else { "s" },
+            self.physical_cores,
+            self.logical_cpus,
+            nodes.join(", ")
+        )
+    }
+}
+
+impl std::fmt::Display for NumaTopology {
+    /// Deployment type and totals, then one table row per node
+    ///
+    /// Memory shows `-` when it was not detected.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.deployment_type())?;
+        writeln!(
+            f,
+            "{} node(s), {} physical cores, {} logical CPUs",
+            self.num_nodes, self.physical_cores, self.logical_cpus
+        )?;
+        write!(f, "{:>6}  {:>5}  {:>10}", "node", "cpus", "memory")?;
+        for node in &self.nodes {
+            let memory = if node.memory_gb > 0.0 {
+                format!("{:.1} GB", node.memory_gb)
+            } else {
+                "-".to_string()
+            };
+            write!(
+                f,
+                "\n{:>6}  {:>5}  {:>10}",
+                node.node_id,
+                node.cpus.len(),
+                memory
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Topology detected once per process, shared by every generation call
+static DETECTED_TOPOLOGY: OnceLock<Option<NumaTopology>> = OnceLock::new();
+
+/// System topology, detected on first use and cached for the process lifetime
+///
+/// Returns None if detection failed. Generation calls use this instead of running
+/// hwloc discovery each time.
+pub fn detected_topology() -> Option<&'static NumaTopology> {
+    DETECTED_TOPOLOGY
+        .get_or_init(|| match NumaTopology::detect() {
+            Ok(topology) => {
+                if topology.is_uma {
+                    tracing::info!(
+                        "NUMA support compiled in but hardware is UMA: NUMA code paths disabled"
+                    );
+                }
+                Some(topology)
+            }
+            Err(e) => {
+                tracing::warn!("NUMA topology detection failed: {}, treating as UMA", e);
+                None
+            }
+        })
+        .as_ref()
+}
+
+/// True when the machine has a single NUMA node (or detection failed)
+///
+/// In that case `NumaMode::Auto` generation skips all NUMA work (topology lookups,
+/// thread pinning, first-touch) as if `NumaMode::Disabled` had been passed.
+pub fn is_effective_uma() -> bool {
+    detected_topology().is_none_or(|topology| topology.is_uma)
+}
+
+/// Memory allocated through hwloc together with the topology it was allocated from
+///
+/// hwloc's `Bytes<'topology>` borrows the `Topology`, so storing both in one owned
+/// value needs the borrow erased. This type owns the pair and keeps its fields
+/// private: the bytes can only be reached through slices and pointers borrowed
+/// from `self`, and are always freed before the topology reference is released.
+pub struct NumaAllocation {
+    /// Never moved out; dropped in `Drop` while `topology` is still alive
+    bytes: ManuallyDrop<Bytes<'static>>,
+    topology: Arc<Topology>,
+    len: usize,
+    binding: NumaBinding,
+}
+
+impl NumaAllocation {
+    /// Allocate with `alloc` from `topology` and take ownership of both
+    ///
+    /// `len` starts at the full allocation size. hwloc hands out uninitialized
+    /// memory, so it is zeroed here: every slice this type returns is then
+    /// initialized. The binding policy places the pages, so zeroing on the calling
+    /// thread does not move them.
+    pub(crate) fn new<E>(
+        topology: Arc<Topology>,
+        binding: NumaBinding,
+        alloc: impl FnOnce(&Topology) -> std::result::Result<Bytes<'_>, E>,
+    ) -> std::result::Result<Self, E> {
+        let mut bytes = alloc(&topology)?;
+        bytes.fill(MaybeUninit::new(0));
+        // SAFETY: the bytes borrow the Topology behind the Arc, whose heap address is
+        // stable. The Arc is stored alongside and the bytes are dropped first (see
+        // Drop), and no method hands out the Bytes or anything outliving `&self`.
+        let bytes = unsafe { std::mem::transmute::<Bytes<'_>, Bytes<'static>>(bytes) };
+        Ok(Self {
+            len: bytes.len(),
+            bytes: ManuallyDrop::new(bytes),
+            topology,
+            binding,
+        })
+    }
+
+    /// Logical data length (at most `capacity()`)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the logical length is zero
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Set the logical length, clamped to `capacity()` (metadata only)
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len.min(self.capacity());
+    }
+
+    /// Size of the underlying allocation
+    pub fn capacity(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Placement the memory was allocated with
+    pub fn binding(&self) -> &NumaBinding {
+        &self.binding
+    }
+
+    /// Topology the memory was allocated from
+    pub fn topology(&self) -> &Arc<Topology> {
+        &self.topology
+    }
+
+    /// First `len()` bytes
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: len <= capacity, the allocation is zeroed in new() and lives as
+        // long as self
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Whole allocation, mutably (all of `capacity()`)
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: the allocation is zeroed in new(), lives as long as self and is
+        // uniquely borrowed
+        unsafe { std::slice::from_raw_parts_mut(self.as_mut_ptr(), self.capacity()) }
+    }
+
+    /// Start of the allocation
+    pub fn as_ptr(&self) -> *const u8 {
+        self.bytes.as_ptr() as *const u8
+    }
+
+    /// Start of the allocation, for writes
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.bytes.as_mut_ptr() as *mut u8
+    }
+}
+
+impl Drop for NumaAllocation {
+    fn drop(&mut self) {
+        // SAFETY: dropped exactly once, here, before `topology` is released
+        unsafe { ManuallyDrop::drop(&mut self.bytes) }
+    }
+}
+
+impl std::fmt::Debug for NumaAllocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NumaAllocation")
+            .field("ptr", &self.as_ptr())
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("binding", &self.binding)
+            .finish()
+    }
 }
 
 /// Detect number of NUMA nodes
@@ -271,10 +478,130 @@ mod tests {
     fn test_detect_topology() {
         init_tracing();
         if let Ok(topology) = NumaTopology::detect() {
-            println!("NUMA topology: {:?}", topology);
+            println!("NUMA topology:\n{}", topology);
             assert!(topology.num_nodes >= 1);
             assert!(topology.physical_cores >= 1);
             assert!(topology.logical_cpus >= topology.physical_cores);
         }
     }
+
+    #[test]
+    fn test_topology_display() {
+        let topology = NumaTopology {
+            num_nodes: 2,
+            physical_cores: 4,
+            logical_cpus: 8,
+            nodes: vec![
+                NumaNode {
+                    node_id: 0,
+                    cpus: vec![0, 1, 2, 3],
+                    memory_gb: 31.5,
+                },
+                NumaNode {
+                    node_id: 1,
+                    cpus: vec![4, 5, 6, 7],
+                    memory_gb: 0.0,
+                },
+            ],
+            is_uma: false,
+        };
+        assert_eq!(
+            topology.summary(),
+            "2 NUMA nodes, 4 cores / 8 CPUs: node 0 = 4 CPUs, node 1 = 4 CPUs"
+        );
+
+        let table = topology.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], topology.deployment_type());
+        assert_eq!(lines[1], "2 node(s), 4 physical cores, 8 logical CPUs");
+        assert_eq!(lines[3], "     0      4     31.5 GB");
+        assert_eq!(lines[4], "     1      4           -");
+    }
+
+    #[test]
+    fn test_shared_topology() {
+        use crate::generator::DataBuffer;
+        use std::sync::Arc;
+
+        init_tracing();
+        let Ok(topology) = Topology::new() else {
+            return;
+        };
+        let numa = NumaTopology::from_topology(&topology);
+        assert!(numa.num_nodes >= 1);
+
+        // Allocations hold the shared topology only while they are alive
+        let topology = Arc::new(topology);
+        let node_id = numa.nodes[0].node_id;
+        if let Ok(mut buf) = DataBuffer::alloc_numa_with_topology(topology.clone(), 4096, node_id) {
+            assert_eq!(Arc::strong_count(&topology), 2);
+            assert!(buf.as_slice().iter().all(|&b| b == 0));
+            buf.as_mut_slice().fill(0xAB);
+            assert_eq!(buf.len(), 4096);
+            assert_eq!(buf.numa_node(), Some(node_id));
+
+            // Outgrowing the buffer re-allocates it on the same node and topology
+            let config = crate::GeneratorConfig {
+                size: 64 * 1024,
+                seed: Some(1),
+                ..Default::default()
+            };
+            assert_eq!(buf.reset_and_fill(&config), Ok(config.size));
+            assert_eq!(buf.numa_node(), Some(node_id));
+            assert_eq!(Arc::strong_count(&topology), 2);
+        }
+        assert_eq!(Arc::strong_count(&topology), 1);
+    }
+
+    #[test]
+    fn test_numa_allocation_owns_topology() {
+        use hwlocality::memory::{
+            binding::{MemoryBindingFlags, MemoryBindingPolicy},
+            nodeset::NodeSet,
+        };
+
+        init_tracing();
+        let Ok(topology) = Topology::new() else {
+            return;
+        };
+        let Some(node_id) = NumaTopology::from_topology(&topology)
+            .nodes
+            .first()
+            .map(|n| n.node_id)
+        else {
+            return;
+        };
+        let mut nodeset = NodeSet::new();
+        nodeset.set(node_id);
+
+        let topology = Arc::new(topology);
+        let alloc = NumaAllocation::new(topology.clone(), NumaBinding::Node(node_id), |t| {
+            t.binding_allocate_memory(
+                8192,
+                &nodeset,
+                MemoryBindingPolicy::Bind,
+                MemoryBindingFlags::ASSUME_SINGLE_THREAD,
+            )
+        });
+        let Ok(mut alloc) = alloc else {
+            return;
+        };
+        assert!(alloc.as_slice().iter().all(|&b| b == 0));
+
+        // The caller's Arc can go first: the allocation keeps the topology alive
+        let weak = Arc::downgrade(&topology);
+        drop(topology);
+        assert!(weak.upgrade().is_some());
+
+        alloc.as_mut_slice().fill(0x5A);
+        alloc.set_len(100_000);
+        assert_eq!(alloc.len(), 8192);
+        alloc.set_len(4096);
+        assert!(alloc.as_slice().iter().all(|&b| b == 0x5A));
+        assert_eq!(alloc.binding(), &NumaBinding::Node(node_id));
+
+        drop(alloc);
+        assert!(weak.upgrade().is_none());
+    }
 }
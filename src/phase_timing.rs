@@ -0,0 +1,81 @@
+// src/phase_timing.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Per-phase timing of block generation (keystream fill vs compressible zero-fill)
+//!
+//! Enabled with the `phase-timing` feature. Counters are process-wide atomics summed
+//! across all worker threads, so the reported nanoseconds are CPU time, not wall time.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+static KEYSTREAM_NS: AtomicU64 = AtomicU64::new(0);
+static KEYSTREAM_BYTES: AtomicU64 = AtomicU64::new(0);
+static ZERO_FILL_NS: AtomicU64 = AtomicU64::new(0);
+static ZERO_FILL_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Snapshot of cumulative phase timings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    /// Nanoseconds spent filling incompressible bytes with the RNG keystream
+    pub keystream_ns: u64,
+    /// Bytes filled with the RNG keystream
+    pub keystream_bytes: u64,
+    /// Nanoseconds spent zero-filling compressible bytes
+    pub zero_fill_ns: u64,
+    /// Bytes zero-filled
+    pub zero_fill_bytes: u64,
+}
+
+impl PhaseTimings {
+    /// Timings accumulated since an earlier snapshot
+    pub fn since(&self, earlier: &PhaseTimings) -> PhaseTimings {
+        PhaseTimings {
+            keystream_ns: self.keystream_ns.saturating_sub(earlier.keystream_ns),
+            keystream_bytes: self.keystream_bytes.saturating_sub(earlier.keystream_bytes),
+            zero_fill_ns: self.zero_fill_ns.saturating_sub(earlier.zero_fill_ns),
+            zero_fill_bytes: self.zero_fill_bytes.saturating_sub(earlier.zero_fill_bytes),
+        }
+    }
+}
+
+/// Get cumulative phase timings since process start (or the last reset)
+pub fn phase_timings() -> PhaseTimings {
+    PhaseTimings {
+        keystream_ns: KEYSTREAM_NS.load(Ordering::Relaxed),
+        keystream_bytes: KEYSTREAM_BYTES.load(Ordering::Relaxed),
+        zero_fill_ns: ZERO_FILL_NS.load(Ordering::Relaxed),
+        zero_fill_bytes: ZERO_FILL_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Reset all phase counters to zero
+pub fn reset_phase_timings() {
+    KEYSTREAM_NS.store(0, Ordering::Relaxed);
+    KEYSTREAM_BYTES.store(0, Ordering::Relaxed);
+    ZERO_FILL_NS.store(0, Ordering::Relaxed);
+    ZERO_FILL_BYTES.store(0, Ordering::Relaxed);
+}
+
+pub(crate) fn record_keystream(elapsed: Duration, bytes: usize) {
+    KEYSTREAM_NS.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    KEYSTREAM_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+pub(crate) fn record_zero_fill(elapsed: Duration, bytes: usize) {
+    ZERO_FILL_NS.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    ZERO_FILL_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+/// Log the phase timings accumulated since `start` (called at the end of `generate_data`)
+pub(crate) fn log_since(start: &PhaseTimings) {
+    let delta = phase_timings().since(start);
+    tracing::info!(
+        "Phase timing: keystream {} bytes in {} ns, zero-fill {} bytes in {} ns (CPU time)",
+        delta.keystream_bytes,
+        delta.keystream_ns,
+        delta.zero_fill_bytes,
+        delta.zero_fill_ns
+    );
+}
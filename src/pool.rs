@@ -0,0 +1,177 @@
+// src/pool.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! One thread pool shared by many concurrent generation streams
+//!
+//! Every `DataGenerator` builds its own pool on first parallel use, so a server
+//! generating many objects at once would run one pool per object. A
+//! [`GeneratorPool`] owns a single pool and hands out [`StreamHandle`]s, each a
+//! full streaming generator with its own config, seed and position, whose
+//! parallel fills all run on that pool. Total generation threads stay capped at
+//! the pool size however many streams are active.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+#[cfg(not(feature = "parallel"))]
+use crate::sequential as rayon;
+
+use crate::cpus::effective_cpu_count;
+use crate::generator::{thread_pool_builder, DataGenerator, GeneratorConfig};
+
+/// Thread pool shared by any number of [`StreamHandle`]s
+///
+/// Cloning is cheap and shares the same pool. The worker threads exit once the
+/// pool and every handle created from it are dropped.
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{GeneratorConfig, GeneratorPool};
+///
+/// let pool = GeneratorPool::new(Some(4)).unwrap();
+/// std::thread::scope(|s| {
+///     for seed in 0..8 {
+///         let mut stream = pool.stream(GeneratorConfig {
+///             size: 8 * 1024 * 1024,
+///             seed: Some(seed),
+///             ..Default::default()
+///         });
+///         s.spawn(move || {
+///             let mut buf = vec![0u8; 4 * 1024 * 1024];
+///             while !stream.is_complete() {
+///                 stream.fill_chunk(&mut buf);
+///             }
+///         });
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct GeneratorPool {
+    pool: Arc<rayon::ThreadPool>,
+    threads: usize,
+}
+
+impl GeneratorPool {
+    /// Create a pool of `max_threads` workers (None = all cores the process may use)
+    pub fn new(max_threads: Option<usize>) -> std::io::Result<Self> {
+        let threads = max_threads.unwrap_or_else(effective_cpu_count).max(1);
+        let pool = thread_pool_builder(threads, Some("dgen-pool"), None)
+            .build()
+            .map_err(std::io::Error::other)?;
+        tracing::info!("GeneratorPool created with {} threads", threads);
+        Ok(Self {
+            pool: Arc::new(pool),
+            threads,
+        })
+    }
+
+    /// Number of worker threads
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// New stream generating `config` on this pool
+    ///
+    /// The stream behaves like `DataGenerator::new(config)` and produces the same
+    /// bytes; `max_threads`, `cpu_affinity` and `use_global_pool` are ignored
+    /// because the shared pool decides where the work runs.
+    pub fn stream(&self, config: GeneratorConfig) -> StreamHandle {
+        StreamHandle {
+            generator: DataGenerator::with_shared_pool(
+                config,
+                Arc::clone(&self.pool),
+                self.threads,
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for GeneratorPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeneratorPool")
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+/// One stream of a [`GeneratorPool`]
+///
+/// Dereferences to `DataGenerator` for `fill_chunk()`, `generate_range()`,
+/// `set_seed()`, `stats()` and the rest of the streaming API.
+pub struct StreamHandle {
+    generator: DataGenerator,
+}
+
+impl StreamHandle {
+    /// The underlying generator, keeping its reference to the shared pool
+    pub fn into_inner(self) -> DataGenerator {
+        self.generator
+    }
+}
+
+impl Deref for StreamHandle {
+    type Target = DataGenerator;
+
+    fn deref(&self) -> &DataGenerator {
+        &self.generator
+    }
+}
+
+impl DerefMut for StreamHandle {
+    fn deref_mut(&mut self) -> &mut DataGenerator {
+        &mut self.generator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLOCK_SIZE;
+
+    #[test]
+    fn test_streams_share_one_pool() {
+        let pool = GeneratorPool::new(Some(2)).unwrap();
+        let configs: Vec<GeneratorConfig> = (0..3)
+            .map(|n| GeneratorConfig {
+                size: BLOCK_SIZE * 9 + 123,
+                dedup_factor: n + 1,
+                compress_factor: 2,
+                seed: Some(n as u64),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut streams: Vec<StreamHandle> =
+            configs.iter().cloned().map(|c| pool.stream(c)).collect();
+        assert_eq!(Arc::strong_count(&pool.pool), 1 + streams.len());
+
+        let outputs: Vec<Vec<u8>> = std::thread::scope(|s| {
+            let workers: Vec<_> = streams
+                .iter_mut()
+                .map(|stream| {
+                    s.spawn(move || {
+                        let mut out = vec![0u8; stream.total_size()];
+                        for chunk in out.chunks_mut(BLOCK_SIZE * 4) {
+                            stream.fill_chunk(chunk);
+                        }
+                        // release() must not drop the shared pool
+                        stream.release();
+                        out
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        assert_eq!(Arc::strong_count(&pool.pool), 1 + streams.len());
+
+        for (config, output) in configs.into_iter().zip(outputs) {
+            let mut expected = vec![0u8; output.len()];
+            DataGenerator::new(config).fill_chunk(&mut expected);
+            assert_eq!(output, expected);
+        }
+
+        drop(streams);
+        assert_eq!(Arc::strong_count(&pool.pool), 1);
+    }
+}
@@ -9,7 +9,16 @@ use pyo3::ffi;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 
-use crate::generator::{generate_data, DataBuffer, DataGenerator, GeneratorConfig, NumaMode};
+use crate::benchmark::benchmark_with_warmup;
+use crate::content::ContentMode;
+use crate::cpus::effective_cpu_count;
+use crate::generator::{
+    fill_buffers as fill_buffers_rs, generate_buffers, generate_data, generate_range,
+    generate_to_files, verify_range, DataBuffer, DataGenerator, GeneratorConfig, NumaBinding,
+    NumaMode,
+};
+use crate::keystream::KeyStream;
+use crate::ring::{RingBuffer, RingGenerator};
 
 #[cfg(feature = "numa")]
 use crate::numa::NumaTopology;
@@ -22,10 +31,25 @@ use crate::numa::NumaTopology;
 /// This allows Python code to get a memoryview without copying data.
 ///
 /// ZERO-COPY: Python accesses the NUMA-allocated memory directly via raw pointer!
+///
+/// Views are read-only unless created with `writable=True`, in which case
+/// `memoryview(data)` can be used to mutate the generated data in place.
 #[pyclass(name = "BytesView")]
 pub struct PyBytesView {
     /// The underlying DataBuffer (Vec for UMA, hwlocality Bytes for NUMA)
     buffer: DataBuffer,
+    /// Whether the buffer protocol exports a writable buffer
+    writable: bool,
+}
+
+impl PyBytesView {
+    /// Wrap a DataBuffer for zero-copy export to Python
+    ///
+    /// Lets a buffer from `DataBuffer::alloc_uma()` / `alloc_numa()` that was filled
+    /// with custom logic be returned to Python without copying.
+    pub fn new(buffer: DataBuffer, writable: bool) -> Self {
+        PyBytesView { buffer, writable }
+    }
 }
 
 #[pymethods]
@@ -40,33 +64,50 @@ impl PyBytesView {
         PyBytes::new(py, self.buffer.as_slice())
     }
 
+    /// Whether this view exports a writable buffer
+    #[getter]
+    fn writable(&self) -> bool {
+        self.writable
+    }
+
+    /// NUMA node the data lives on, or None (UMA, UMA fallback, or multi-node placement)
+    ///
+    /// Check this after requesting `numa_node=N` to confirm the allocation was not
+    /// silently replaced by a UMA buffer.
+    #[getter]
+    fn numa_node(&self) -> Option<usize> {
+        self.buffer.numa_node()
+    }
+
     /// Implement Python buffer protocol for zero-copy access.
     /// This allows `memoryview(data)` to work directly.
     ///
-    /// The buffer is read-only; requesting a writable buffer will raise BufferError.
+    /// The buffer is read-only unless the view was created with `writable=True`;
+    /// requesting a writable buffer from a read-only view will raise BufferError.
     ///
     /// ZERO-COPY: Python accesses NUMA memory directly via raw pointer!
     unsafe fn __getbuffer__(
-        slf: PyRef<'_, Self>,
+        mut slf: PyRefMut<'_, Self>,
         view: *mut ffi::Py_buffer,
         flags: std::os::raw::c_int,
     ) -> PyResult<()> {
-        // Check for writable request - we only support read-only buffers
-        if (flags & ffi::PyBUF_WRITABLE) != 0 {
+        // Writable requests are only honoured for views created with writable=True
+        if (flags & ffi::PyBUF_WRITABLE) != 0 && !slf.writable {
             return Err(pyo3::exceptions::PyBufferError::new_err(
                 "BytesView is read-only and does not support writable buffers",
             ));
         }
 
-        let buffer = &slf.buffer;
-        let len = buffer.len();
-        let ptr = buffer.as_ptr();
+        let readonly = !slf.writable;
+        let len = slf.buffer.len();
+        // Take the mutable pointer so Python writes are valid for writable views
+        let ptr = slf.buffer.as_mut_ptr();
 
         // Fill in the Py_buffer struct with DataBuffer's raw pointer
         unsafe {
             (*view).buf = ptr as *mut std::os::raw::c_void;
             (*view).len = len as isize;
-            (*view).readonly = 1;
+            (*view).readonly = readonly as std::os::raw::c_int;
             (*view).itemsize = 1;
 
             // Format string: "B" = unsigned byte (matches u8)
@@ -127,8 +168,21 @@ impl PyBytesView {
 /// * `size` - Total bytes to generate
 /// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
 /// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
-/// * `numa_mode` - NUMA mode: "auto", "force", or "disabled" (default: "auto")
-/// * `max_threads` - Maximum threads to use (None = use all cores)
+/// * `numa_mode` - NUMA mode: "auto", "force", "disabled", or "interleave" (default: "auto")
+/// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+/// * `numa_node` - Bind to a NUMA node or list of nodes, e.g. `[0, 2]` (None = all nodes)
+/// * `writable` - Export a writable buffer so the data can be modified in place (default: False)
+/// * `content` - Data layout: "random" (default), "csv[:COLUMNS:FIELD_LEN]", "dict[:NUM_VALUES:VALUE_LEN]" or "pseudo[:PERIOD|TARGET]"
+///   (TARGET picks the period for a compressor: "deflate", "zstd", "lz4" or "generic")
+/// * `seed` - Seed for reproducible data (None = fresh entropy per call)
+/// * `alignment` - Align the buffer address for O_DIRECT, e.g. 4096, and round the
+///   size up to a multiple of it (None = no alignment)
+/// * `cpu_affinity` - Pin worker threads to these CPU IDs, one thread per CPU by
+///   default (None = no explicit pinning)
+/// * `compressible_fraction` - Compressible share of each block in [0, 1], e.g.
+///   0.7 for "70% compressible"; overrides `compress_ratio` (None = use the ratio)
+/// * `unique_prefix_bytes` - Bytes at the start of every block that are unique to
+///   that block, so duplicates only match past them (0 = none)
 ///
 /// # Returns
 /// Python bytes object with generated data (zero-copy from Rust)
@@ -136,6 +190,10 @@ impl PyBytesView {
 /// # Note
 /// Ratios must be integers >= 1. Floats will be truncated with a warning.
 ///
+/// With `seed` set, the output is byte-identical to Rust's
+/// `generate_data(GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, Some(seed)))`
+/// with the same `max_threads`.
+///
 /// # Example
 /// ```python
 /// import dgen_py
@@ -146,7 +204,8 @@ impl PyBytesView {
 /// print(f"Generated {len(data)} bytes")
 /// ```
 #[pyfunction]
-#[pyo3(signature = (size, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None))]
+#[pyo3(signature = (size, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None, writable=false, content="random", seed=None, alignment=None, cpu_affinity=None, compressible_fraction=None, unique_prefix_bytes=0))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
 fn generate_buffer(
     py: Python<'_>,
     size: usize,
@@ -154,59 +213,39 @@ fn generate_buffer(
     compress_ratio: f64,
     numa_mode: &str,
     max_threads: Option<usize>,
-    numa_node: Option<usize>,
+    numa_node: Option<NumaNodeArg>,
+    writable: bool,
+    content: &str,
+    seed: Option<u64>,
+    alignment: Option<usize>,
+    cpu_affinity: Option<Vec<usize>>,
+    compressible_fraction: Option<f64>,
+    unique_prefix_bytes: usize,
 ) -> PyResult<Py<PyBytesView>> {
-    // Warn if floats are being truncated
-    if dedup_ratio.fract() != 0.0 {
-        let truncated = dedup_ratio as usize;
-        let warnings = py.import("warnings")?;
-        warnings.call_method1(
-            "warn",
-            (format!(
-                "dedup_ratio={:.2} truncated to integer {} (fractional ratios not supported)",
-                dedup_ratio, truncated
-            ),),
-        )?;
-    }
-    if compress_ratio.fract() != 0.0 {
-        let truncated = compress_ratio as usize;
-        let warnings = py.import("warnings")?;
-        warnings.call_method1(
-            "warn",
-            (format!(
-                "compress_ratio={:.2} truncated to integer {} (fractional ratios not supported)",
-                compress_ratio, truncated
-            ),),
-        )?;
-    }
-
-    // Convert ratios to integer factors
-    let dedup = (dedup_ratio.max(1.0) as usize).max(1);
-    let compress = (compress_ratio.max(1.0) as usize).max(1);
-
-    // Parse NUMA mode
-    let numa = match numa_mode.to_lowercase().as_str() {
-        "auto" => NumaMode::Auto,
-        "force" => NumaMode::Force,
-        "disabled" | "disable" => NumaMode::Disabled,
-        _ => {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Invalid numa_mode '{}': must be 'auto', 'force', or 'disabled'",
-                numa_mode
-            )))
-        }
-    };
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+    check_cpu_affinity(&cpu_affinity)?;
+    check_compressible_fraction(compressible_fraction)?;
+    if alignment == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "alignment must be non-zero",
+        ));
+    }
 
-    // Build config
+    // Parse NUMA mode and node binding
+    let (numa, numa_binding) = parse_numa(numa_mode, numa_node)?;
+
+    // Build config (ratio conversion shared with Rust callers via from_ratios)
     let config = GeneratorConfig {
-        size,
-        dedup_factor: dedup,
-        compress_factor: compress,
         numa_mode: numa,
         max_threads,
-        numa_node, // CRITICAL: Use the parameter to bind to specific NUMA node
-        block_size: None,
-        seed: None,
+        numa_binding,
+        content: parse_content(content)?,
+        alignment,
+        round_up_to_alignment: true,
+        cpu_affinity,
+        compressible_fraction,
+        unique_prefix_bytes,
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
     };
 
     // Generate data WITHOUT holding GIL (allows parallel Python threads)
@@ -215,7 +254,13 @@ fn generate_buffer(
 
     // Return PyBytesView with DataBuffer directly - ZERO COPY!
     // Python accesses the memory via memoryview() using raw pointer from DataBuffer
-    Py::new(py, PyBytesView { buffer: data })
+    Py::new(
+        py,
+        PyBytesView {
+            buffer: data,
+            writable,
+        },
+    )
 }
 
 /// Generate data using Python buffer protocol (for writing into existing buffer)
@@ -224,8 +269,10 @@ fn generate_buffer(
 /// * `buffer` - Pre-allocated Python buffer (bytearray, memoryview, numpy array, etc.)
 /// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
 /// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
-/// * `numa_mode` - NUMA mode: "auto", "force", or "disabled" (default: "auto")
-/// * `max_threads` - Maximum threads to use (None = use all cores)
+/// * `numa_mode` - NUMA mode: "auto", "force", "disabled", or "interleave" (default: "auto")
+/// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+/// * `numa_node` - Bind to a NUMA node or list of nodes, e.g. `[0, 2]` (None = all nodes)
+/// * `seed` - Seed for reproducible data (None = fresh entropy per call)
 ///
 /// # Returns
 /// Number of bytes written
@@ -246,7 +293,8 @@ fn generate_buffer(
 /// print(f"Wrote {nbytes} bytes")
 /// ```
 #[pyfunction]
-#[pyo3(signature = (buffer, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None))]
+#[pyo3(signature = (buffer, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None, seed=None))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
 fn generate_into_buffer(
     py: Python<'_>,
     buffer: &Bound<'_, PyAny>,
@@ -254,7 +302,8 @@ fn generate_into_buffer(
     compress_ratio: f64,
     numa_mode: &str,
     max_threads: Option<usize>,
-    numa_node: Option<usize>,
+    numa_node: Option<NumaNodeArg>,
+    seed: Option<u64>,
 ) -> PyResult<usize> {
     // Get buffer via PyBuffer protocol
     let buf: PyBuffer<u8> = PyBuffer::get(buffer)?;
@@ -272,57 +321,19 @@ fn generate_into_buffer(
         ));
     }
 
-    // Warn if floats are being truncated
-    if dedup_ratio.fract() != 0.0 {
-        let truncated = dedup_ratio as usize;
-        let warnings = py.import("warnings")?;
-        warnings.call_method1(
-            "warn",
-            (format!(
-                "dedup_ratio={:.2} truncated to integer {} (fractional ratios not supported)",
-                dedup_ratio, truncated
-            ),),
-        )?;
-    }
-    if compress_ratio.fract() != 0.0 {
-        let truncated = compress_ratio as usize;
-        let warnings = py.import("warnings")?;
-        warnings.call_method1(
-            "warn",
-            (format!(
-                "compress_ratio={:.2} truncated to integer {} (fractional ratios not supported)",
-                compress_ratio, truncated
-            ),),
-        )?;
-    }
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
 
     let size = buf.len_bytes();
-    let dedup = (dedup_ratio.max(1.0) as usize).max(1);
-    let compress = (compress_ratio.max(1.0) as usize).max(1);
-
-    // Parse NUMA mode
-    let numa = match numa_mode.to_lowercase().as_str() {
-        "auto" => NumaMode::Auto,
-        "force" => NumaMode::Force,
-        "disabled" | "disable" => NumaMode::Disabled,
-        _ => {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Invalid numa_mode '{}': must be 'auto', 'force', or 'disabled'",
-                numa_mode
-            )))
-        }
-    };
+
+    // Parse NUMA mode and node binding
+    let (numa, numa_binding) = parse_numa(numa_mode, numa_node)?;
 
     // Build config
     let config = GeneratorConfig {
-        size,
-        dedup_factor: dedup,
-        compress_factor: compress,
         numa_mode: numa,
         max_threads,
-        numa_node, // CRITICAL: Bind to specific NUMA node if specified
-        block_size: None,
-        seed: None,
+        numa_binding,
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
     };
 
     // Generate data
@@ -337,6 +348,456 @@ fn generate_into_buffer(
     Ok(size)
 }
 
+/// Generate one slice of a deterministic dataset into an existing buffer (scatter)
+///
+/// Writes bytes `[logical_offset, logical_offset + len(buffer))` of the dataset
+/// defined by `dataset_size`, the ratios, `seed`, `block_size` and `content`: exactly
+/// the bytes `generate_buffer()` with the same parameters would place there. Parallel
+/// workers can each fill their own slice of a large array and get a reproducible
+/// result regardless of how the work is split.
+///
+/// # Arguments
+/// * `buffer` - Pre-allocated writable, C-contiguous buffer (bytearray, memoryview slice, numpy array, etc.)
+/// * `logical_offset` - Dataset offset of the first byte of `buffer`
+/// * `dataset_size` - Total size of the logical dataset
+/// * `seed` - Dataset seed (required: every slice must use the same one)
+/// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
+/// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+/// * `block_size` - Internal block size (None = auto, must match across slices)
+/// * `content` - Data layout: "random" (default), "csv[:COLUMNS:FIELD_LEN]", "dict[:NUM_VALUES:VALUE_LEN]" or "pseudo[:PERIOD|TARGET]"
+///
+/// # Returns
+/// Number of bytes written (less than `len(buffer)` if the slice extends past the
+/// end of the dataset)
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// size = 1024**3
+/// data = bytearray(size)
+/// view = memoryview(data)
+/// half = size // 2
+/// # Each half could be filled by a different worker thread
+/// dgen_py.generate_into_buffer_at(view[:half], 0, size, seed=42)
+/// dgen_py.generate_into_buffer_at(view[half:], half, size, seed=42)
+/// ```
+#[pyfunction]
+#[pyo3(signature = (buffer, logical_offset, dataset_size, seed, dedup_ratio=1.0, compress_ratio=1.0, block_size=None, content="random"))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+fn generate_into_buffer_at(
+    py: Python<'_>,
+    buffer: &Bound<'_, PyAny>,
+    logical_offset: u64,
+    dataset_size: usize,
+    seed: u64,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    block_size: Option<usize>,
+    content: &str,
+) -> PyResult<usize> {
+    let buf: PyBuffer<u8> = PyBuffer::get(buffer)?;
+
+    if buf.readonly() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Buffer must be writable",
+        ));
+    }
+
+    if !buf.is_c_contiguous() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Buffer must be C-contiguous for zero-copy operation",
+        ));
+    }
+
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+
+    let config = GeneratorConfig {
+        block_size,
+        content: parse_content(content)?,
+        ..GeneratorConfig::from_ratios(dataset_size, dedup_ratio, compress_ratio, Some(seed))
+    };
+
+    let size = buf.len_bytes();
+
+    // ZERO-COPY: Generate DIRECTLY into Python buffer without holding GIL
+    let written = py.detach(|| unsafe {
+        let dst_slice = std::slice::from_raw_parts_mut(buf.buf_ptr() as *mut u8, size);
+        generate_range(&config, logical_offset, dst_slice)
+    });
+
+    Ok(written)
+}
+
+/// Verify a read-back buffer against the deterministic dataset it should contain
+///
+/// Regenerates `[logical_offset, logical_offset + len(buffer))` in Rust and compares
+/// without holding the GIL, using only a small scratch buffer. Parameters describe
+/// the dataset exactly as for `generate_into_buffer_at()`.
+///
+/// # Arguments
+/// * `buffer` - C-contiguous buffer holding the data read back (bytes, bytearray, memoryview, numpy array, etc.)
+/// * `logical_offset` - Dataset offset of the first byte of `buffer`
+/// * `dedup_ratio` - Deduplication ratio the data was generated with
+/// * `compress_ratio` - Compression ratio the data was generated with
+/// * `seed` - Seed the data was generated with
+/// * `dataset_size` - Total dataset size (None = `logical_offset + len(buffer)`)
+/// * `block_size` - Internal block size the data was generated with (None = auto)
+/// * `content` - Data layout the data was generated with (default: "random")
+///
+/// # Returns
+/// `(True, None)` if every byte matches, otherwise `(False, offset)` with the dataset
+/// offset of the first mismatching byte
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// size = 64 * 1024**2
+/// data = bytearray(size)
+/// dgen_py.generate_into_buffer_at(data, 0, size, seed=42, compress_ratio=2)
+/// # ... write to storage and read back into `data` ...
+/// ok, offset = dgen_py.verify_buffer(data, compress_ratio=2, seed=42)
+/// assert ok, f"corruption at byte {offset}"
+/// ```
+#[pyfunction]
+#[pyo3(signature = (buffer, logical_offset=0, dedup_ratio=1.0, compress_ratio=1.0, *, seed, dataset_size=None, block_size=None, content="random"))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+fn verify_buffer(
+    py: Python<'_>,
+    buffer: &Bound<'_, PyAny>,
+    logical_offset: u64,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    seed: u64,
+    dataset_size: Option<usize>,
+    block_size: Option<usize>,
+    content: &str,
+) -> PyResult<(bool, Option<u64>)> {
+    let buf: PyBuffer<u8> = PyBuffer::get(buffer)?;
+
+    if !buf.is_c_contiguous() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Buffer must be C-contiguous",
+        ));
+    }
+
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+
+    let len = buf.len_bytes();
+    let size = dataset_size.unwrap_or(logical_offset as usize + len);
+    let config = GeneratorConfig {
+        block_size,
+        content: parse_content(content)?,
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, Some(seed))
+    };
+
+    // Compare without holding the GIL
+    let mismatch = py.detach(|| unsafe {
+        let data = std::slice::from_raw_parts(buf.buf_ptr() as *const u8, len);
+        verify_range(&config, logical_offset, data)
+    });
+
+    Ok((mismatch.is_none(), mismatch))
+}
+
+/// Generate `size` bytes of raw Xoshiro256++ keystream
+///
+/// The same stream the generator uses for incompressible data, without any
+/// dedup/compress shaping: useful for test buffers and fuzzing inputs. Generated
+/// single-threaded.
+///
+/// # Arguments
+/// * `size` - Number of bytes
+/// * `seed` - Seed for reproducible bytes (None = fresh entropy per call)
+///
+/// # Returns
+/// BytesView with the generated bytes; `random_bytes(n, seed=s)` matches Rust's
+/// `KeyStream::new(s).fill()` over `n` bytes
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// payload = bytes(dgen_py.random_bytes(4096, seed=1))
+/// ```
+#[pyfunction]
+#[pyo3(signature = (size, seed=None))]
+fn random_bytes(py: Python<'_>, size: usize, seed: Option<u64>) -> PyResult<Py<PyBytesView>> {
+    let data = py.detach(|| {
+        let mut stream = seed.map_or_else(KeyStream::from_entropy, KeyStream::new);
+        let mut buf = vec![0u8; size];
+        stream.fill(&mut buf);
+        buf
+    });
+    Py::new(py, PyBytesView::new(DataBuffer::Uma(data), false))
+}
+
+/// Seed for shard `shard_id` of dataset `dataset_id`
+///
+/// Pass it as `seed` so every worker assigned a shard generates the same bytes;
+/// `Generator.generate_range()` produces any part of a shard at its absolute
+/// offset. The mixing function is fixed across versions (see the Rust
+/// `keyed_seed()` docs for the formula).
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// seed = dgen_py.keyed_seed(dataset_id=7, shard_id=3)
+/// data = dgen_py.generate_buffer(64 * 1024**2, seed=seed)
+/// ```
+#[pyfunction]
+#[pyo3(name = "keyed_seed")]
+fn py_keyed_seed(dataset_id: u64, shard_id: u64) -> u64 {
+    crate::generator::keyed_seed(dataset_id, shard_id)
+}
+
+/// Bytes `generate_buffer()` allocates for the same arguments
+///
+/// The data is generated in whole blocks and the buffer is allocated up front, so
+/// peak memory is `size` rounded up to the block size (and `alignment`), at least
+/// one block. Check it against available memory before a large one-shot call
+/// (`fits_in_memory()` does); `Generator` streams the same data through a
+/// fixed-size chunk instead.
+///
+/// # Arguments
+/// * `size` - Total bytes to generate
+/// * `max_threads` - As passed to `generate_buffer()` (it selects the block size of
+///   unseeded data; seeded data uses 1 MiB blocks and needs at most this)
+/// * `alignment` - As passed to `generate_buffer()` (None = no alignment)
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// size = 64 * 1024**3
+/// print(f"generate_buffer({size}) needs {dgen_py.estimated_peak_bytes(size)} bytes")
+/// ```
+#[pyfunction]
+#[pyo3(signature = (size, max_threads=None, alignment=None))]
+fn estimated_peak_bytes(
+    size: usize,
+    max_threads: Option<usize>,
+    alignment: Option<usize>,
+) -> PyResult<usize> {
+    Ok(oneshot_config(size, max_threads, alignment)?.estimated_peak_bytes())
+}
+
+/// Whether `generate_buffer()` with these arguments fits in available memory
+///
+/// Compares `estimated_peak_bytes()` with the memory the kernel reports as
+/// available (`MemAvailable` on Linux). True when that cannot be determined.
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// size = 500 * 1024**3
+/// if dgen_py.fits_in_memory(size):
+///     data = dgen_py.generate_buffer(size)
+/// else:
+///     gen = dgen_py.Generator(size)  # stream instead
+/// ```
+#[pyfunction]
+#[pyo3(signature = (size, max_threads=None, alignment=None))]
+fn fits_in_memory(
+    size: usize,
+    max_threads: Option<usize>,
+    alignment: Option<usize>,
+) -> PyResult<bool> {
+    Ok(oneshot_config(size, max_threads, alignment)?.fits_in_memory())
+}
+
+/// Largest `size` whose `generate_buffer()` allocation fits in available memory
+///
+/// A whole number of blocks for the given `max_threads`; leave headroom for the
+/// rest of the process. None when available memory cannot be determined.
+#[pyfunction]
+#[pyo3(signature = (max_threads=None))]
+fn max_safe_oneshot_size(max_threads: Option<usize>) -> PyResult<Option<usize>> {
+    let size = oneshot_config(0, max_threads, None)?.max_safe_oneshot_size();
+    Ok((size != usize::MAX).then_some(size))
+}
+
+/// Config `generate_buffer()` builds for the sizing helpers
+fn oneshot_config(
+    size: usize,
+    max_threads: Option<usize>,
+    alignment: Option<usize>,
+) -> PyResult<GeneratorConfig> {
+    if alignment == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "alignment must be non-zero",
+        ));
+    }
+    Ok(GeneratorConfig {
+        max_threads,
+        alignment,
+        round_up_to_alignment: true,
+        ..GeneratorConfig::from_ratios(size, 1.0, 1.0, None)
+    })
+}
+
+pyo3::create_exception!(
+    _dgen_rs,
+    DgenRatioTruncationWarning,
+    pyo3::exceptions::PyUserWarning,
+    "Issued when a fractional dedup/compress ratio is truncated to an integer factor."
+);
+
+/// Warn (as `DgenRatioTruncationWarning`) about fractional ratios being truncated
+///
+/// Attributed to the calling Python line (`stacklevel=1` for a builtin is the
+/// equivalent of `stacklevel=2` from Python), so the default `warnings` filter
+/// shows it once per call site instead of once per call in a batch loop.
+/// Silence it with `warnings.simplefilter("ignore", dgen_py.DgenRatioTruncationWarning)`.
+fn warn_ratio_truncation(py: Python<'_>, dedup_ratio: f64, compress_ratio: f64) -> PyResult<()> {
+    for (name, ratio) in [
+        ("dedup_ratio", dedup_ratio),
+        ("compress_ratio", compress_ratio),
+    ] {
+        if ratio.fract() != 0.0 {
+            let message = std::ffi::CString::new(format!(
+                "{}={:.2} truncated to integer {} (fractional ratios not supported)",
+                name, ratio, ratio as usize
+            ))?;
+            PyErr::warn(
+                py,
+                &py.get_type::<DgenRatioTruncationWarning>(),
+                &message,
+                1,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pyo3::create_exception!(
+    _dgen_rs,
+    DgenChunkSizeWarning,
+    pyo3::exceptions::PyUserWarning,
+    "Issued when a Generator chunk_size is adjusted to avoid a pathological size."
+);
+
+pyo3::create_exception!(
+    _dgen_rs,
+    DgenParallelismWarning,
+    pyo3::exceptions::PyUserWarning,
+    "Issued when a Generator block_size leaves each chunk too few blocks for its threads."
+);
+
+/// Clamp an explicit Generator `chunk_size` to `[block_size, total_size]`
+///
+/// Chunks smaller than a block regenerate that block on every call (throughput
+/// collapses), and chunks larger than the whole stream only waste the caller's
+/// buffer. Both are bumped into range with a `DgenChunkSizeWarning`, attributed to
+/// the calling line like `warn_ratio_truncation`. When the stream is smaller than
+/// one block the upper bound wins, since the whole stream fits in a single chunk.
+fn checked_chunk_size(
+    py: Python<'_>,
+    chunk_size: usize,
+    block_size: usize,
+    total_size: usize,
+) -> PyResult<usize> {
+    let max = total_size.max(1);
+    let adjusted = chunk_size.max(block_size).min(max);
+    if adjusted != chunk_size {
+        let reason = if chunk_size < adjusted {
+            "smaller than the generator block size, so every call would regenerate a block"
+        } else {
+            "larger than the whole stream"
+        };
+        let message = std::ffi::CString::new(format!(
+            "chunk_size={} is {}; using {}",
+            chunk_size, reason, adjusted
+        ))?;
+        PyErr::warn(py, &py.get_type::<DgenChunkSizeWarning>(), &message, 1)?;
+    }
+    Ok(adjusted)
+}
+
+/// `numa_node` argument: a single node or a list of nodes
+#[derive(FromPyObject)]
+enum NumaNodeArg {
+    One(usize),
+    Many(Vec<usize>),
+}
+
+/// Parse `numa_mode` ("auto", "force", "disabled", "interleave") and `numa_node`
+/// (None, a node id, or a list of node ids) into a mode and binding
+fn parse_numa(
+    numa_mode: &str,
+    numa_node: Option<NumaNodeArg>,
+) -> PyResult<(NumaMode, NumaBinding)> {
+    let (mode, interleave) = match numa_mode.to_lowercase().as_str() {
+        "auto" => (NumaMode::Auto, false),
+        "force" => (NumaMode::Force, false),
+        "disabled" | "disable" => (NumaMode::Disabled, false),
+        "interleave" => (NumaMode::Auto, true),
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid numa_mode '{}': must be 'auto', 'force', 'disabled', or 'interleave'",
+                numa_mode
+            )))
+        }
+    };
+
+    let binding = match (interleave, numa_node) {
+        (true, None) => NumaBinding::Interleave,
+        (true, Some(_)) => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "numa_node cannot be combined with numa_mode='interleave'",
+            ))
+        }
+        (false, None) => NumaBinding::All,
+        (false, Some(NumaNodeArg::One(node))) => NumaBinding::Node(node),
+        (false, Some(NumaNodeArg::Many(nodes))) => NumaBinding::Nodes(nodes),
+    };
+
+    Ok((mode, binding))
+}
+
+/// Reject an empty `cpu_affinity` list, which would leave no CPU to run on
+fn check_cpu_affinity(cpu_affinity: &Option<Vec<usize>>) -> PyResult<()> {
+    if cpu_affinity.as_ref().is_some_and(|cpus| cpus.is_empty()) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            crate::generator::ConfigError::EmptyCpuAffinity.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a `max_gbps` rate that is not positive and finite
+fn check_max_gbps(max_gbps: Option<f64>) -> PyResult<()> {
+    if max_gbps.is_some_and(|gbps| !(gbps.is_finite() && gbps > 0.0)) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            crate::generator::ConfigError::InvalidMaxGbps.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a `compressible_fraction` outside [0, 1]
+fn check_compressible_fraction(fraction: Option<f64>) -> PyResult<()> {
+    match fraction {
+        Some(f) if !(0.0..=1.0).contains(&f) => {
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                crate::generator::ConfigError::InvalidCompressibleFraction(f.to_string())
+                    .to_string(),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parse a `content` argument ("random", "csv[:COLUMNS:FIELD_LEN]", "dict[:NUM_VALUES:VALUE_LEN]", "pseudo[:PERIOD|TARGET]")
+fn parse_content(content: &str) -> PyResult<ContentMode> {
+    content.parse().map_err(|e: crate::generator::ConfigError| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+    })
+}
+
 // =============================================================================
 // Streaming API - Generator class
 // =============================================================================
@@ -368,6 +829,10 @@ fn generate_into_buffer(
 ///
 /// print(f"Generated {total} bytes")
 /// ```
+/// Alignment of the `fill_fd()` write buffer; covers O_DIRECT on 4 KiB-sector devices
+#[cfg(unix)]
+const PAGE_ALIGN: usize = 4096;
+
 #[pyclass(name = "Generator")]
 struct PyGenerator {
     inner: DataGenerator,
@@ -382,12 +847,27 @@ impl PyGenerator {
     /// * `size` - Total bytes to generate
     /// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
     /// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
-    /// * `numa_mode` - NUMA mode: "auto", "force", or "disabled" (default: "auto")
-    /// * `max_threads` - Maximum threads to use (None = use all cores)
-    /// * `numa_node` - Pin to specific NUMA node (None = use all nodes, 0-N = specific node)
-    /// * `chunk_size` - Chunk size for streaming (default: 32 MB for optimal performance)
+    /// * `numa_mode` - NUMA mode: "auto", "force", "disabled", or "interleave" (default: "auto")
+    /// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+    /// * `numa_node` - Pin to specific NUMA node (None = use all nodes, 0-N = specific node, list = subset)
+    /// * `chunk_size` - Chunk size for streaming (default: 32 MB for optimal performance).
+    ///   Explicit values are kept within [block size, total size]; out-of-range values
+    ///   are adjusted with a `DgenChunkSizeWarning`
     /// * `block_size` - Internal parallelization block size (default: 4 MB, max: 32 MB)
     /// * `seed` - Random seed for reproducible data (None = use time + urandom for non-deterministic)
+    /// * `content` - Data layout: "random" (default), "csv[:COLUMNS:FIELD_LEN]", "dict[:NUM_VALUES:VALUE_LEN]" or "pseudo[:PERIOD|TARGET]"
+    /// * `cpu_affinity` - Pin worker threads to these CPU IDs, one thread per CPU by
+    ///   default (None = no explicit pinning)
+    /// * `max_gbps` - Pace `fill_chunk()` to this many GB (10^9 bytes) per second by
+    ///   sleeping after each chunk, with bursts of up to 100 ms of data (None = unpaced)
+    /// * `compressible_fraction` - Compressible share of each block in [0, 1], e.g.
+    ///   0.7 for "70% compressible"; overrides `compress_ratio` (None = use the ratio)
+    /// * `unique_prefix_bytes` - Bytes at the start of every block that are unique to
+    ///   that block, so duplicates only match past them (0 = none)
+    /// * `fit_block_size` - Shrink the block size (down to 256 KiB) until every chunk
+    ///   has a block per thread. Changes the data for a given seed, so it is off by
+    ///   default; without it, an explicit `block_size` that leaves threads idle (e.g.
+    ///   32 MB blocks in 32 MB chunks) raises a `DgenParallelismWarning`
     ///
     /// # Note on Ratios
     /// Both dedup_ratio and compress_ratio MUST be integers >= 1.
@@ -398,7 +878,7 @@ impl PyGenerator {
     /// When seed is provided, Generator produces identical data for the same configuration.
     /// This enables reproducible testing and benchmarking.
     #[new]
-    #[pyo3(signature = (size, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None, chunk_size=None, block_size=None, seed=None))]
+    #[pyo3(signature = (size, dedup_ratio=1.0, compress_ratio=1.0, numa_mode="auto", max_threads=None, numa_node=None, chunk_size=None, block_size=None, seed=None, content="random", cpu_affinity=None, max_gbps=None, compressible_fraction=None, unique_prefix_bytes=0, fit_block_size=false))]
     #[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
     fn new(
         py: Python<'_>,
@@ -407,66 +887,65 @@ impl PyGenerator {
         compress_ratio: f64,
         numa_mode: &str,
         max_threads: Option<usize>,
-        numa_node: Option<usize>,
+        numa_node: Option<NumaNodeArg>,
         chunk_size: Option<usize>,
         block_size: Option<usize>,
         seed: Option<u64>,
+        content: &str,
+        cpu_affinity: Option<Vec<usize>>,
+        max_gbps: Option<f64>,
+        compressible_fraction: Option<f64>,
+        unique_prefix_bytes: usize,
+        fit_block_size: bool,
     ) -> PyResult<Self> {
-        // Warn if floats are being truncated
-        if dedup_ratio.fract() != 0.0 {
-            let truncated = dedup_ratio as usize;
-            let warnings = py.import("warnings")?;
-            warnings.call_method1(
-                "warn",
-                (format!(
-                    "dedup_ratio={:.2} truncated to integer {} (fractional ratios not supported)",
-                    dedup_ratio, truncated
-                ),),
-            )?;
-        }
-        if compress_ratio.fract() != 0.0 {
-            let truncated = compress_ratio as usize;
-            let warnings = py.import("warnings")?;
-            warnings.call_method1(
-                "warn",
-                (format!("compress_ratio={:.2} truncated to integer {} (fractional ratios not supported)", 
-                         compress_ratio, truncated),)
-            )?;
-        }
+        warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+        check_cpu_affinity(&cpu_affinity)?;
+        check_max_gbps(max_gbps)?;
+        check_compressible_fraction(compressible_fraction)?;
 
-        let dedup = (dedup_ratio.max(1.0) as usize).max(1);
-        let compress = (compress_ratio.max(1.0) as usize).max(1);
-
-        // Parse NUMA mode
-        let numa = match numa_mode.to_lowercase().as_str() {
-            "auto" => NumaMode::Auto,
-            "force" => NumaMode::Force,
-            "disabled" | "disable" => NumaMode::Disabled,
-            _ => {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Invalid numa_mode '{}': must be 'auto', 'force', or 'disabled'",
-                    numa_mode
-                )))
-            }
-        };
+        // Parse NUMA mode and node binding
+        let (numa, numa_binding) = parse_numa(numa_mode, numa_node)?;
 
-        let config = GeneratorConfig {
-            size,
-            dedup_factor: dedup,
-            compress_factor: compress,
+        let mut config = GeneratorConfig {
             numa_mode: numa,
             max_threads,
-            numa_node,
+            numa_binding,
             block_size,
-            seed,
+            content: parse_content(content)?,
+            cpu_affinity,
+            max_gbps,
+            compressible_fraction,
+            unique_prefix_bytes,
+            ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
+        };
+        let explicit_block_size = config.block_size.is_some();
+        let fitted = config
+            .block_size_for_chunk(chunk_size.unwrap_or_else(DataGenerator::recommended_chunk_size));
+        if fit_block_size {
+            config.block_size = Some(fitted);
+        }
+
+        let inner = DataGenerator::new(config);
+        let chunk_size = match chunk_size {
+            Some(size) => checked_chunk_size(py, size, inner.block_size(), inner.total_size())?,
+            None => DataGenerator::recommended_chunk_size(),
         };
 
-        let chunk_size = chunk_size.unwrap_or_else(DataGenerator::recommended_chunk_size);
+        // Only an explicit block_size is at fault; the default is only shrunk on request
+        if explicit_block_size && fitted < inner.block_size() && chunk_size < inner.total_size() {
+            let message = std::ffi::CString::new(format!(
+                "chunk_size={} holds {} block(s) of block_size={}, fewer than the generator's \
+                 threads; use chunk_size >= {}, block_size={} or fit_block_size=True",
+                chunk_size,
+                chunk_size.div_ceil(inner.block_size()),
+                inner.block_size(),
+                inner.parallel_chunk_size(),
+                fitted
+            ))?;
+            PyErr::warn(py, &py.get_type::<DgenParallelismWarning>(), &message, 1)?;
+        }
 
-        Ok(Self {
-            inner: DataGenerator::new(config),
-            chunk_size,
-        })
+        Ok(Self { inner, chunk_size })
     }
 
     /// Get recommended chunk size for optimal performance (32 MB)
@@ -513,24 +992,153 @@ impl PyGenerator {
         Ok(written)
     }
 
-    /// Get data as BytesView (zero-copy access via memoryview)
+    /// Check that `buffer` holds the next `len(buffer)` bytes of the stream
+    ///
+    /// Regenerates the expected bytes at the current position into a reused
+    /// scratch buffer of at most one chunk and compares without holding the GIL,
+    /// so data read back from storage can be verified chunk by chunk. The position
+    /// advances past `buffer` whether or not it matches.
     ///
     /// # Arguments
-    /// * `chunk_size` - Size of chunk to read
+    /// * `buffer` - C-contiguous buffer holding the data read back
     ///
     /// # Returns
-    /// BytesView object or None if complete
-    fn get_chunk(
-        &mut self,
-        py: Python<'_>,
-        chunk_size: usize,
-    ) -> PyResult<Option<Py<PyBytesView>>> {
-        if self.inner.is_complete() {
-            return Ok(None);
-        }
-
-        let mut chunk = vec![0u8; chunk_size];
-        let written = self.inner.fill_chunk(&mut chunk);
+    /// `(True, None)` if every byte matches, otherwise `(False, offset)` with the
+    /// stream offset of the first mismatching byte (the stream size if `buffer`
+    /// runs past the end)
+    ///
+    /// # Example
+    /// ```python
+    /// verifier = dgen_py.Generator(size, seed=42)
+    /// with open("/mnt/test/file", "rb") as f:
+    ///     while chunk := f.read(verifier.chunk_size):
+    ///         ok, offset = verifier.verify_chunk(chunk)
+    ///         assert ok, f"corruption at byte {offset}"
+    /// ```
+    fn verify_chunk(&mut self, py: Python<'_>, buffer: Py<PyAny>) -> PyResult<(bool, Option<u64>)> {
+        let buf: PyBuffer<u8> = PyBuffer::get(buffer.bind(py))?;
+
+        if !buf.is_c_contiguous() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Buffer must be C-contiguous",
+            ));
+        }
+
+        let len = buf.len_bytes();
+        // Compare without holding the GIL
+        let result = py.detach(|| unsafe {
+            let data = std::slice::from_raw_parts(buf.buf_ptr() as *const u8, len);
+            self.inner.verify_chunk(data)
+        });
+
+        Ok(match result {
+            Ok(()) => (true, None),
+            Err(e) => (false, Some(e.offset())),
+        })
+    }
+
+    /// Generate the next `count` bytes and write them to file descriptor `fd`
+    ///
+    /// Chunks are generated into a reused, page-aligned buffer (usable with
+    /// `O_DIRECT`) and written from Rust without holding the GIL, so no chunk
+    /// passes through Python. Short writes and `EINTR` are retried. With `offset`
+    /// the data is written with `pwrite` starting at that file offset and the
+    /// descriptor's file position is left alone; otherwise `write` is used.
+    ///
+    /// The descriptor is borrowed, not closed. With `O_DIRECT`, `offset` and every
+    /// write length must meet the device alignment, so keep `count` a multiple of
+    /// it (the chunk size already is).
+    ///
+    /// # Arguments
+    /// * `fd` - Open, writable file descriptor (e.g. `os.open(...)` or `f.fileno()`)
+    /// * `count` - Number of bytes to write
+    /// * `offset` - File offset for positional writes (None = current position)
+    ///
+    /// # Returns
+    /// Number of bytes written: `count`, or less if the stream completes first
+    ///
+    /// # Raises
+    /// OSError if a write fails; the stream has then advanced past the failed chunk
+    ///
+    /// # Example
+    /// ```python
+    /// fd = os.open("/mnt/test/file", os.O_WRONLY | os.O_CREAT | os.O_DIRECT)
+    /// gen = dgen_py.Generator(size=100 * 1024**3)
+    /// while not gen.is_complete():
+    ///     gen.fill_fd(fd, 1024**3)
+    /// os.close(fd)
+    /// ```
+    #[cfg(unix)]
+    #[pyo3(signature = (fd, count, offset=None))]
+    fn fill_fd(
+        &mut self,
+        py: Python<'_>,
+        fd: i32,
+        count: usize,
+        offset: Option<u64>,
+    ) -> PyResult<usize> {
+        use std::io::Write;
+        use std::mem::ManuallyDrop;
+        use std::os::unix::fs::FileExt;
+        use std::os::unix::io::FromRawFd;
+
+        if fd < 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "invalid file descriptor {}",
+                fd
+            )));
+        }
+
+        let inner = &mut self.inner;
+        let chunk_size = self.chunk_size;
+        let written = py.detach(|| -> std::io::Result<usize> {
+            // SAFETY: the caller owns `fd`; ManuallyDrop keeps it open on return
+            let mut file = ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(fd) });
+
+            let count = count.min(inner.total_size().saturating_sub(inner.position()));
+            let len = chunk_size.min(count);
+            // Over-allocate by one page and start the slice on a page boundary
+            let mut storage = vec![0u8; len + PAGE_ALIGN];
+            let start = storage.as_ptr().align_offset(PAGE_ALIGN);
+            let buffer = &mut storage[start..start + len];
+
+            let mut written = 0;
+            while written < count {
+                let want = (count - written).min(buffer.len());
+                let nbytes = inner.fill_chunk(&mut buffer[..want]);
+                if nbytes == 0 {
+                    break;
+                }
+                match offset {
+                    Some(base) => file.write_all_at(&buffer[..nbytes], base + written as u64)?,
+                    None => file.write_all(&buffer[..nbytes])?,
+                }
+                written += nbytes;
+            }
+            Ok(written)
+        })?;
+
+        Ok(written)
+    }
+
+    /// Get data as BytesView (zero-copy access via memoryview)
+    ///
+    /// # Arguments
+    /// * `chunk_size` - Size of chunk to read
+    ///
+    /// # Returns
+    /// BytesView object or None if complete
+    fn get_chunk(
+        &mut self,
+        py: Python<'_>,
+        chunk_size: usize,
+    ) -> PyResult<Option<Py<PyBytesView>>> {
+        if self.inner.is_complete() {
+            return Ok(None);
+        }
+
+        let mut chunk = vec![0u8; chunk_size];
+        let written = self.inner.fill_chunk(&mut chunk);
 
         if written == 0 {
             Ok(None)
@@ -538,15 +1146,66 @@ impl PyGenerator {
             chunk.truncate(written);
             // Wrap in DataBuffer::Uma for zero-copy Python access
             let buffer = DataBuffer::Uma(chunk);
-            Ok(Some(Py::new(py, PyBytesView { buffer })?))
+            Ok(Some(Py::new(
+                py,
+                PyBytesView {
+                    buffer,
+                    writable: false,
+                },
+            )?))
         }
     }
 
+    /// Read an arbitrary range of the stream as a BytesView (zero-copy)
+    ///
+    /// Returns the bytes at `[offset, offset + length)` exactly as the stream would
+    /// produce them under the current seed, without moving the read position. Useful
+    /// for serving random reads (e.g. ranged GETs) of a deterministic dataset.
+    ///
+    /// # Arguments
+    /// * `offset` - Byte offset into the stream
+    /// * `length` - Number of bytes to read
+    ///
+    /// # Returns
+    /// BytesView of the range, truncated at `total_size()` (empty past the end)
+    fn read_at(&self, py: Python<'_>, offset: usize, length: usize) -> PyResult<Py<PyBytesView>> {
+        let length = length.min(self.inner.total_size().saturating_sub(offset));
+        let mut buffer = DataBuffer::alloc_uma(length);
+
+        // Generate without holding the GIL
+        py.detach(|| {
+            self.inner.generate_range(offset, buffer.as_mut_slice());
+        });
+
+        Py::new(py, PyBytesView::new(buffer, false))
+    }
+
     /// Reset generator to start
     fn reset(&mut self) {
         self.inner.reset();
     }
 
+    /// Rewind to the start and switch to fresh time + urandom entropy
+    ///
+    /// Each call yields a different dataset of the same shape without rebuilding
+    /// the generator and its thread pool, e.g. one per benchmark iteration.
+    ///
+    /// # Returns
+    /// The new seed; pass it as `seed` to reproduce that iteration
+    ///
+    /// # Example
+    /// ```python
+    /// gen = dgen_py.Generator(size=10 * 1024**3)
+    /// buffer = bytearray(gen.chunk_size)
+    /// for _ in range(5):
+    ///     seed = gen.reset_with_new_seed()
+    ///     while not gen.is_complete():
+    ///         gen.fill_chunk(buffer)
+    /// ```
+    fn reset_with_new_seed(&mut self) -> u64 {
+        self.inner.reset_with_new_seed()
+    }
+
     /// Get current position
     fn position(&self) -> usize {
         self.inner.position()
@@ -562,6 +1221,98 @@ impl PyGenerator {
         self.inner.is_complete()
     }
 
+    /// Release the worker thread pool and cached partial block
+    ///
+    /// A long-lived generator otherwise keeps its threads after the stream is done.
+    /// Safe to call at any position and more than once; the generator stays usable
+    /// and rebuilds the pool on the next parallel fill.
+    fn close(&mut self) {
+        self.inner.release();
+    }
+
+    /// Stream statistics: progress, realized ratios and throughput
+    ///
+    /// # Returns
+    /// Dict with `bytes_generated`, `blocks_generated`, `cache_hits` (partial blocks
+    /// copied from the cached last block instead of regenerated), `threads_pinned`
+    /// and `threads_total` (pool threads pinned to a `cpu_affinity` CPU, of all pool
+    /// threads: fewer means pinning was refused), `requested_dedup`,
+    /// `requested_compress`, `realized_dedup` (block level), `realized_compress`,
+    /// `page_dedup_estimate`, `effective_seed` (the seed in use, also when none was
+    /// given: pass it as `seed` to replay the stream), `numa_compiled`, `numa_active`
+    /// (False on single-node hardware even when compiled in) and, once any data has
+    /// been generated, `gbps` (time spent inside the generator only, excluding
+    /// Python-side work)
+    ///
+    /// `page_dedup_estimate` is the dedup a storage array fingerprinting 4 KiB pages
+    /// would report. The compressible part of every block is zeros, and zero pages
+    /// from different unique blocks dedup against each other, so combining
+    /// `dedup_ratio=2` with `compress_ratio=4` shows up as roughly 8:1 there.
+    ///
+    /// # Example
+    /// ```python
+    /// gen = dgen_py.Generator(size=10*1024**3, dedup_ratio=2, compress_ratio=3)
+    /// buffer = bytearray(gen.chunk_size)
+    /// while not gen.is_complete():
+    ///     gen.fill_chunk(buffer)
+    /// stats = gen.stats()
+    /// print(f"{stats['gbps']:.2f} GB/s, dedup {stats['realized_dedup']:.2f}:1")
+    /// ```
+    fn stats(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        use pyo3::types::PyDict;
+
+        let stats = self.inner.stats();
+        let dict = PyDict::new(py);
+        dict.set_item("bytes_generated", stats.bytes_generated)?;
+        dict.set_item("blocks_generated", stats.blocks_generated)?;
+        dict.set_item("cache_hits", stats.cache_hits)?;
+        dict.set_item("threads_pinned", stats.threads_pinned)?;
+        dict.set_item("threads_total", stats.threads_total)?;
+        dict.set_item("requested_dedup", stats.requested_dedup)?;
+        dict.set_item("requested_compress", stats.requested_compress)?;
+        dict.set_item("realized_dedup", stats.realized_dedup)?;
+        dict.set_item("realized_compress", stats.realized_compress)?;
+        dict.set_item("page_dedup_estimate", stats.page_dedup_estimate)?;
+        dict.set_item("effective_seed", stats.effective_seed)?;
+        dict.set_item("numa_compiled", stats.numa_compiled)?;
+        dict.set_item("numa_active", stats.numa_active)?;
+        if let Some(gbps) = stats.gbps() {
+            dict.set_item("gbps", gbps)?;
+        }
+
+        Ok(dict.into())
+    }
+
+    /// Settings in use after normalization and clamping
+    ///
+    /// # Returns
+    /// Dict with `total_size`, `block_size` (after clamping to 256 KiB-32 MiB), `nblocks`,
+    /// `unique_blocks`, `max_threads`, `dedup_factor`, `compress_factor` and `seed`
+    /// (the seed in use, also when none was given)
+    ///
+    /// # Example
+    /// ```python
+    /// gen = dgen_py.Generator(size=64*1024**2, block_size=256*1024)
+    /// print(gen.effective_config["block_size"])  # 1048576
+    /// ```
+    #[getter]
+    fn effective_config(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        use pyo3::types::PyDict;
+
+        let config = self.inner.effective_config();
+        let dict = PyDict::new(py);
+        dict.set_item("total_size", config.total_size)?;
+        dict.set_item("block_size", config.block_size)?;
+        dict.set_item("nblocks", config.nblocks)?;
+        dict.set_item("unique_blocks", config.unique_blocks)?;
+        dict.set_item("max_threads", config.max_threads)?;
+        dict.set_item("dedup_factor", config.dedup_factor)?;
+        dict.set_item("compress_factor", config.compress_factor)?;
+        dict.set_item("seed", config.seed)?;
+
+        Ok(dict.into())
+    }
+
     /// Set or reset the random seed for subsequent data generation
     ///
     /// This allows changing the data pattern mid-stream while maintaining generation position.
@@ -593,6 +1344,269 @@ impl PyGenerator {
     }
 }
 
+// =============================================================================
+// Ring Buffer API
+// =============================================================================
+
+/// How long `RingGenerator.__next__` waits for a free buffer between signal checks
+const RING_WAIT_SLICE: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Bounded producer that fills a fixed set of reusable buffers in rotation
+///
+/// Iterating yields `RingBuffer`s in stream order. Each one occupies a slot until
+/// it is released (`release()`, leaving a `with` block, or being garbage
+/// collected); once all `slots` are held, the next iteration blocks until one is
+/// released, e.g. by a consumer thread. Memory stays at `slots * chunk_size`.
+///
+/// # Example
+/// ```python
+/// ring = dgen_py.RingGenerator(size=10 * 1024**3, slots=4, seed=42)
+/// for buf in ring:
+///     with buf:
+///         f.write(memoryview(buf))
+/// ```
+#[pyclass(name = "RingGenerator")]
+struct PyRingGenerator {
+    inner: RingGenerator,
+}
+
+#[pymethods]
+impl PyRingGenerator {
+    /// Create a ring producer
+    ///
+    /// # Arguments
+    /// * `size` - Total bytes to generate
+    /// * `slots` - Number of reusable buffers (default: 4)
+    /// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
+    /// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+    /// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+    /// * `chunk_size` - Size of each buffer (default: 32 MB, capped at `size`)
+    /// * `block_size` - Internal parallelization block size (default: 4 MB, max: 32 MB)
+    /// * `seed` - Random seed for reproducible data (None = use time + urandom)
+    /// * `content` - Data layout, as for `Generator`
+    /// * `max_gbps` - Pace generation to this many GB (10^9 bytes) per second (None = unpaced)
+    /// * `compressible_fraction` - Compressible share of each block in [0, 1];
+    ///   overrides `compress_ratio` (None = use the ratio)
+    /// * `unique_prefix_bytes` - Bytes at the start of every block unique to that block (0 = none)
+    #[new]
+    #[pyo3(signature = (size, slots=4, dedup_ratio=1.0, compress_ratio=1.0, max_threads=None, chunk_size=None, block_size=None, seed=None, content="random", max_gbps=None, compressible_fraction=None, unique_prefix_bytes=0))]
+    #[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+    fn new(
+        py: Python<'_>,
+        size: usize,
+        slots: usize,
+        dedup_ratio: f64,
+        compress_ratio: f64,
+        max_threads: Option<usize>,
+        chunk_size: Option<usize>,
+        block_size: Option<usize>,
+        seed: Option<u64>,
+        content: &str,
+        max_gbps: Option<f64>,
+        compressible_fraction: Option<f64>,
+        unique_prefix_bytes: usize,
+    ) -> PyResult<Self> {
+        warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+        check_max_gbps(max_gbps)?;
+        check_compressible_fraction(compressible_fraction)?;
+        if slots == 0 || chunk_size == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "slots and chunk_size must be at least 1",
+            ));
+        }
+
+        let config = GeneratorConfig {
+            max_threads,
+            block_size,
+            content: parse_content(content)?,
+            max_gbps,
+            compressible_fraction,
+            unique_prefix_bytes,
+            ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
+        };
+
+        // Allocating the buffers touches every page; do it without the GIL
+        let inner = py.detach(|| RingGenerator::new(config, slots, chunk_size.unwrap_or(0)));
+        Ok(Self { inner })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Fill the next free buffer, waiting for one to be released if needed
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyRingBuffer>> {
+        if self.inner.is_complete() {
+            return Ok(None);
+        }
+        // Wait in slices so Ctrl-C still works while every buffer is held
+        while !py.detach(|| self.inner.wait_free(RING_WAIT_SLICE)) {
+            py.check_signals()?;
+        }
+        let buffer = py.detach(|| self.inner.next_buffer());
+        Ok(buffer.map(|buffer| PyRingBuffer {
+            inner: Some(buffer),
+            exports: 0,
+        }))
+    }
+
+    /// Number of buffers in the ring
+    #[getter]
+    fn slots(&self) -> usize {
+        self.inner.slots()
+    }
+
+    /// Size of each buffer (every chunk but possibly the last)
+    #[getter]
+    fn chunk_size(&self) -> usize {
+        self.inner.chunk_size()
+    }
+
+    /// Buffers not currently held by the consumer
+    fn free_slots(&self) -> usize {
+        self.inner.free_slots()
+    }
+
+    /// Get current position
+    fn position(&self) -> usize {
+        self.inner.generator().position()
+    }
+
+    /// Get total size
+    fn total_size(&self) -> usize {
+        self.inner.generator().total_size()
+    }
+
+    /// Check if generation is complete
+    fn is_complete(&self) -> bool {
+        self.inner.is_complete()
+    }
+}
+
+/// One filled chunk of a `RingGenerator`, exposed through the buffer protocol
+///
+/// `memoryview(buf)` reads the ring's memory without copying. Call `release()`
+/// (or use `with buf:`) once done so the producer can refill the slot; releasing
+/// while a memoryview is still open raises BufferError.
+#[pyclass(name = "RingBuffer")]
+struct PyRingBuffer {
+    inner: Option<RingBuffer>,
+    exports: usize, // Live buffer protocol views
+}
+
+impl PyRingBuffer {
+    fn buffer(&self) -> PyResult<&RingBuffer> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("RingBuffer has been released"))
+    }
+}
+
+#[pymethods]
+impl PyRingBuffer {
+    /// Number of valid bytes (0 once released)
+    fn __len__(&self) -> usize {
+        self.inner.as_ref().map_or(0, |buffer| buffer.len())
+    }
+
+    /// Support bytes() conversion - returns a copy
+    fn __bytes__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, self.buffer()?))
+    }
+
+    /// Stream position of the first byte of this chunk
+    #[getter]
+    fn offset(&self) -> PyResult<u64> {
+        Ok(self.buffer()?.offset())
+    }
+
+    /// Whether the slot has been returned to the ring
+    #[getter]
+    fn released(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Return the slot to the ring for refilling; later calls do nothing
+    fn release(&mut self) -> PyResult<()> {
+        if self.exports > 0 {
+            return Err(pyo3::exceptions::PyBufferError::new_err(
+                "RingBuffer still has exported memoryviews",
+            ));
+        }
+        self.inner = None;
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<Bound<'_, PyAny>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.release()?;
+        Ok(false)
+    }
+
+    /// Export the chunk read-only; the slot cannot be released while exported
+    unsafe fn __getbuffer__(
+        mut slf: PyRefMut<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: std::os::raw::c_int,
+    ) -> PyResult<()> {
+        if (flags & ffi::PyBUF_WRITABLE) != 0 {
+            return Err(pyo3::exceptions::PyBufferError::new_err(
+                "RingBuffer is read-only",
+            ));
+        }
+        let buffer = slf.buffer()?;
+        let ptr = buffer.as_ptr();
+        let len = buffer.len();
+        slf.exports += 1;
+
+        unsafe {
+            (*view).buf = ptr as *mut std::os::raw::c_void;
+            (*view).len = len as isize;
+            (*view).readonly = 1;
+            (*view).itemsize = 1;
+            (*view).format = if (flags & ffi::PyBUF_FORMAT) != 0 {
+                c"B".as_ptr() as *mut std::os::raw::c_char
+            } else {
+                std::ptr::null_mut()
+            };
+            (*view).ndim = 1;
+            (*view).shape = if (flags & ffi::PyBUF_ND) != 0 {
+                &(*view).len as *const isize as *mut isize
+            } else {
+                std::ptr::null_mut()
+            };
+            (*view).strides = if (flags & ffi::PyBUF_STRIDES) != 0 {
+                &(*view).itemsize as *const isize as *mut isize
+            } else {
+                std::ptr::null_mut()
+            };
+            (*view).suboffsets = std::ptr::null_mut();
+            (*view).internal = std::ptr::null_mut();
+
+            // Keep this object, and so the slot, alive while the view exists
+            #[allow(clippy::unnecessary_cast)]
+            {
+                (*view).obj = slf.as_ptr() as *mut ffi::PyObject;
+            }
+            ffi::Py_INCREF((*view).obj);
+        }
+
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(mut slf: PyRefMut<'_, Self>, _view: *mut ffi::Py_buffer) {
+        slf.exports -= 1;
+    }
+}
+
 // =============================================================================
 // NUMA Info API
 // =============================================================================
@@ -609,8 +1623,41 @@ fn get_numa_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
     dict.set_item("num_nodes", topology.num_nodes)?;
     dict.set_item("physical_cores", topology.physical_cores)?;
     dict.set_item("logical_cpus", topology.logical_cpus)?;
+    dict.set_item("effective_cpus", effective_cpu_count())?;
     dict.set_item("is_uma", topology.is_uma)?;
     dict.set_item("deployment_type", topology.deployment_type())?;
+    dict.set_item("summary", topology.summary())?;
+
+    Ok(dict.into())
+}
+
+/// Minimal NUMA info for builds without the `numa` feature
+///
+/// Reports a single UMA node so Python code sees the same API regardless of build features.
+#[cfg(not(feature = "numa"))]
+#[pyfunction]
+fn get_numa_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
+    use pyo3::types::PyDict;
+
+    let dict = PyDict::new(py);
+    dict.set_item("num_nodes", 1)?;
+    dict.set_item("physical_cores", num_cpus::get_physical())?;
+    dict.set_item("logical_cpus", num_cpus::get())?;
+    dict.set_item("effective_cpus", effective_cpu_count())?;
+    dict.set_item("is_uma", true)?;
+    dict.set_item(
+        "deployment_type",
+        "UMA (NUMA detection not compiled in - built without 'numa' feature)",
+    )?;
+    dict.set_item(
+        "summary",
+        format!(
+            "1 NUMA node, {} cores / {} CPUs: node 0 = {} CPUs",
+            num_cpus::get_physical(),
+            num_cpus::get(),
+            num_cpus::get()
+        ),
+    )?;
 
     Ok(dict.into())
 }
@@ -649,6 +1696,9 @@ fn get_numa_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
 /// - Direct page cache interaction
 ///
 /// **No custom allocator (jemalloc/mimalloc) needed** - glibc's mmap path is optimal for large buffers!
+/// These bytearrays come from Python's allocator, so the crate's `jemalloc`/`mimalloc`
+/// features don't apply here; they only help Rust-side allocations (e.g. one-shot
+/// `generate_buffer`) and musl builds, where the system malloc is the bottleneck.
 ///
 /// # Why not use mmap directly?
 /// PyByteArray doesn't support custom deallocators, so we'd have to:
@@ -713,26 +1763,450 @@ fn create_bytearrays(py: Python<'_>, count: usize, size: usize) -> PyResult<Py<P
     Ok(list.into())
 }
 
+/// Pre-allocate multiple buffers bound to one NUMA node (returns writable BytesViews)
+///
+/// The NUMA counterpart of `create_bytearrays()`: bytearrays come from glibc malloc
+/// and cannot be bound to a node, so these are hwloc allocations bound to `node`
+/// (one topology discovery shared by all of them), exposed as zero-copy writable
+/// `BytesView`s. Fill them with `Generator.fill_chunk()` or `generate_into_buffer()`
+/// and their pages stay on `node`. Buffers start zeroed, so reading one before it
+/// is filled never exposes earlier process memory.
+///
+/// Raises `RuntimeError` if the allocation fails or the extension was built
+/// without NUMA support.
+///
+/// # Arguments
+/// * `count` - Number of buffers to allocate
+/// * `size` - Size of each buffer in bytes
+/// * `node` - NUMA node to bind the memory to
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// chunks = dgen_py.create_numa_buffers(count=64, size=32*1024**2, node=1)
+/// gen = dgen_py.Generator(size=2*1024**3, numa_node=1)
+/// for buf in chunks:
+///     gen.fill_chunk(buf)
+/// ```
+#[cfg(feature = "numa")]
+#[pyfunction]
+fn create_numa_buffers(
+    py: Python<'_>,
+    count: usize,
+    size: usize,
+    node: usize,
+) -> PyResult<Py<PyAny>> {
+    use pyo3::types::PyList;
+    use std::sync::Arc;
+
+    let buffers = py
+        .detach(|| -> Result<Vec<DataBuffer>, String> {
+            let topology = Arc::new(
+                hwlocality::Topology::new()
+                    .map_err(|e| format!("Failed to create hwloc topology: {}", e))?,
+            );
+            // Each allocation is zeroed (and so first-touched) here, off the GIL,
+            // before Python can read it
+            (0..count)
+                .map(|_| DataBuffer::alloc_numa_with_topology(Arc::clone(&topology), size, node))
+                .collect()
+        })
+        .map_err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>)?;
+
+    let list = PyList::empty(py);
+    for buffer in buffers {
+        list.append(Py::new(
+            py,
+            PyBytesView {
+                buffer,
+                writable: true,
+            },
+        )?)?;
+    }
+    Ok(list.into())
+}
+
+/// Pre-allocate NUMA-bound buffers (unavailable: built without the `numa` feature)
+#[cfg(not(feature = "numa"))]
+#[pyfunction]
+fn create_numa_buffers(count: usize, size: usize, node: usize) -> PyResult<Py<PyAny>> {
+    let _ = (count, size, node); // Same keyword arguments as the NUMA build
+    Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+        "create_numa_buffers requires NUMA support (built without the 'numa' feature)",
+    ))
+}
+
+/// Allocate and fill multiple buffers in a single call (returns zero-copy BytesViews)
+///
+/// Combines `create_bytearrays()` and a fill loop: all buffers are allocated in Rust and
+/// generated in parallel on one thread pool without holding the GIL. This avoids the
+/// per-buffer Python loop and GIL reacquisition that dominates when creating hundreds
+/// of small objects.
+///
+/// # Arguments
+/// * `count` - Number of buffers to create
+/// * `size` - Size of each buffer in bytes (exact, not rounded up to a block)
+/// * `dedup_ratio` - Deduplication ratio within each buffer (integer: 1 = no dedup)
+/// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+/// * `seed` - Random seed for reproducible data (None = use time + urandom)
+/// * `max_threads` - Maximum threads to use (None = rayon's global pool, shared by
+///   every call and sized to the cores the process may use)
+/// * `block_size` - Internal block size (default: 1 MB, max: 32 MB)
+///
+/// # Returns
+/// Python list of BytesView objects (use memoryview() for zero-copy access)
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// # 500 × 64 KiB objects, 2:1 compressible, reproducible
+/// objs = dgen_py.create_and_generate(500, 64 * 1024, compress_ratio=2, seed=42)
+/// for obj in objs:
+///     upload(memoryview(obj))
+/// ```
+#[pyfunction]
+#[pyo3(signature = (count, size, dedup_ratio=1.0, compress_ratio=1.0, seed=None, max_threads=None, block_size=None))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+fn create_and_generate(
+    py: Python<'_>,
+    count: usize,
+    size: usize,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    seed: Option<u64>,
+    max_threads: Option<usize>,
+    block_size: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    use pyo3::types::PyList;
+
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+
+    let config = GeneratorConfig {
+        max_threads,
+        block_size,
+        use_global_pool: max_threads.is_none(),
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
+    };
+
+    // Allocate and generate all buffers WITHOUT holding the GIL
+    let buffers = py.detach(|| generate_buffers(&config, count));
+
+    let list = PyList::empty(py);
+    for buffer in buffers {
+        list.append(Py::new(
+            py,
+            PyBytesView {
+                buffer,
+                writable: false,
+            },
+        )?)?;
+    }
+
+    Ok(list.into())
+}
+
+/// Fill a list of pre-allocated buffers in parallel (single call, GIL released)
+///
+/// Complements `create_bytearrays()`: instead of one `fill_chunk()` call per chunk,
+/// all buffers are filled in one call, distributed across the Rust thread pool.
+///
+/// # Arguments
+/// * `buffers` - List of writable, C-contiguous buffers (bytearray, memoryview, numpy, etc.)
+/// * `dedup_ratio` - Deduplication ratio within each buffer (integer: 1 = no dedup)
+/// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+/// * `seed` - Random seed for reproducible data (None = use time + urandom)
+/// * `max_threads` - Maximum threads to use (None = rayon's global pool, shared by
+///   every call and sized to the cores the process may use)
+/// * `block_size` - Internal block size (default: 1 MB, max: 32 MB)
+///
+/// # Returns
+/// Total number of bytes written
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// chunks = dgen_py.create_bytearrays(count=768, size=32*1024**2)
+/// total = dgen_py.fill_buffers(chunks, compress_ratio=2)
+/// ```
+#[pyfunction]
+#[pyo3(signature = (buffers, dedup_ratio=1.0, compress_ratio=1.0, seed=None, max_threads=None, block_size=None))]
+fn fill_buffers(
+    py: Python<'_>,
+    buffers: Vec<Bound<'_, PyAny>>,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    seed: Option<u64>,
+    max_threads: Option<usize>,
+    block_size: Option<usize>,
+) -> PyResult<usize> {
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+
+    // Acquire all buffers up front; PyBuffer keeps each export alive until we return
+    let mut views: Vec<PyBuffer<u8>> = Vec::with_capacity(buffers.len());
+    for buffer in &buffers {
+        let buf: PyBuffer<u8> = PyBuffer::get(buffer)?;
+        if buf.readonly() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Buffer must be writable",
+            ));
+        }
+        if !buf.is_c_contiguous() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Buffer must be C-contiguous for zero-copy operation",
+            ));
+        }
+        views.push(buf);
+    }
+
+    // Reject overlapping buffers - they would alias mutable slices during parallel fill
+    // (order is preserved for the fill so seeded output doesn't depend on addresses)
+    let ranges: Vec<(usize, usize)> = views
+        .iter()
+        .map(|b| (b.buf_ptr() as usize, b.len_bytes()))
+        .collect();
+    let mut sorted: Vec<(usize, usize)> = ranges.iter().copied().filter(|r| r.1 > 0).collect();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|w| w[0].0 + w[0].1 > w[1].0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Buffers must not overlap",
+        ));
+    }
+
+    // Size is unused: each buffer is filled to its own length
+    let config = GeneratorConfig {
+        max_threads,
+        block_size,
+        use_global_pool: max_threads.is_none(),
+        ..GeneratorConfig::from_ratios(0, dedup_ratio, compress_ratio, seed)
+    };
+
+    // Generate DIRECTLY into Python buffers without holding GIL
+    let written = py.detach(|| {
+        let mut slices: Vec<&mut [u8]> = ranges
+            .iter()
+            .map(|&(ptr, len)| {
+                // SAFETY: Each range comes from a live, writable, contiguous PyBuffer held
+                // in `views`, and the ranges were checked not to overlap
+                unsafe { std::slice::from_raw_parts_mut(ptr as *mut u8, len) }
+            })
+            .collect();
+        fill_buffers_rs(&config, &mut slices)
+    });
+
+    drop(views);
+    Ok(written)
+}
+
+/// Generate a file of `size` bytes entirely in Rust (single call, GIL released)
+///
+/// Streams the data through a reused chunk buffer into the file, so nothing is
+/// held in memory beyond one chunk per call and no Python code runs per chunk.
+/// The file contains the same bytes as `generate_buffer()` with the same seed,
+/// ratios, content and `max_threads`.
+///
+/// # Arguments
+/// * `path` - File to create (truncated if it exists)
+/// * `size` - Bytes to write
+/// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
+/// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+/// * `seed` - Seed for reproducible data (None = fresh entropy per call)
+/// * `chunk_size` - Bytes generated and written per `write()` (None = 32 MiB)
+/// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+/// * `content` - Content mode, as for `generate_buffer()` (default: "random")
+///
+/// # Returns
+/// `(bytes_written, elapsed_seconds)`
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// written, secs = dgen_py.generate_to_file("/tmp/test.bin", 1024**3, compress_ratio=2)
+/// print(f"{written / secs / 1e9:.2f} GB/s")
+/// ```
+#[pyfunction]
+#[pyo3(signature = (path, size, dedup_ratio=1.0, compress_ratio=1.0, seed=None, chunk_size=None, max_threads=None, content="random"))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+fn generate_to_file(
+    py: Python<'_>,
+    path: std::path::PathBuf,
+    size: usize,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    seed: Option<u64>,
+    chunk_size: Option<usize>,
+    max_threads: Option<usize>,
+    content: &str,
+) -> PyResult<(u64, f64)> {
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+    if chunk_size == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "chunk_size must be non-zero",
+        ));
+    }
+
+    let config = GeneratorConfig {
+        max_threads,
+        content: parse_content(content)?,
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
+    };
+
+    let start = std::time::Instant::now();
+    let written = py.detach(|| {
+        generate_to_files(
+            &config,
+            std::slice::from_ref(&path),
+            chunk_size.unwrap_or(0),
+        )
+    })?;
+    Ok((written, start.elapsed().as_secs_f64()))
+}
+
+// =============================================================================
+// Benchmark API
+// =============================================================================
+
+/// Measure generation throughput (warmup + timed iterations, GIL released)
+///
+/// The whole loop runs in Rust, so the timings include no per-iteration Python
+/// overhead, and the warmup runs keep thread-pool startup and first-touch page
+/// faults out of the numbers.
+///
+/// # Arguments
+/// * `size` - Bytes generated per iteration
+/// * `dedup_ratio` - Deduplication ratio (integer: 1 = no dedup, 2 = 2:1 ratio, etc.)
+/// * `compress_ratio` - Compression ratio (integer: 1 = incompressible, 2 = 2:1 ratio, etc.)
+/// * `iterations` - Number of timed iterations (default: 5)
+/// * `max_threads` - Maximum threads to use (None = all cores the process may use)
+/// * `block_size` - Internal block size (None = auto)
+/// * `warmup` - Untimed iterations run first (default: 1; 0 = time the cold start too)
+/// * `numa_mode` - NUMA mode: "auto", "force", "disabled", or "interleave" (default: "auto")
+/// * `seed` - Seed for reproducible data (None = fresh entropy per run)
+/// * `content` - Content mode, as for `generate_buffer()` (default: "random")
+/// * `compressible_fraction` - Fraction of each block filled compressibly (None = from compress_ratio)
+/// * `unique_prefix_bytes` - Unique bytes stamped at the start of every block (default: 0)
+///
+/// # Returns
+/// Dict with keys: iterations, warmup, bytes_per_iteration, threads, total_gbps,
+/// avg_gbps (same as total_gbps), per_core_gbps, min_gbps, max_gbps, stddev_gbps
+///
+/// # Example
+/// ```python
+/// import dgen_py
+///
+/// stats = dgen_py.benchmark(1024**3, iterations=5, warmup=2)
+/// print(f"{stats['avg_gbps']:.2f} GB/s ({stats['per_core_gbps']:.2f} per core)")
+/// ```
+#[pyfunction]
+#[pyo3(signature = (size, dedup_ratio=1.0, compress_ratio=1.0, iterations=5, max_threads=None, block_size=None, warmup=1, numa_mode="auto", seed=None, content="random", compressible_fraction=None, unique_prefix_bytes=0))]
+#[allow(clippy::too_many_arguments)] // PyO3 API requires all parameters as function arguments
+fn benchmark(
+    py: Python<'_>,
+    size: usize,
+    dedup_ratio: f64,
+    compress_ratio: f64,
+    iterations: usize,
+    max_threads: Option<usize>,
+    block_size: Option<usize>,
+    warmup: usize,
+    numa_mode: &str,
+    seed: Option<u64>,
+    content: &str,
+    compressible_fraction: Option<f64>,
+    unique_prefix_bytes: usize,
+) -> PyResult<Py<PyAny>> {
+    use pyo3::types::PyDict;
+
+    warn_ratio_truncation(py, dedup_ratio, compress_ratio)?;
+    check_compressible_fraction(compressible_fraction)?;
+    let (numa, _) = parse_numa(numa_mode, None)?;
+
+    let config = GeneratorConfig {
+        numa_mode: numa,
+        max_threads,
+        block_size,
+        content: parse_content(content)?,
+        compressible_fraction,
+        unique_prefix_bytes,
+        ..GeneratorConfig::from_ratios(size, dedup_ratio, compress_ratio, seed)
+    };
+
+    let result = py.detach(|| benchmark_with_warmup(&config, iterations, warmup));
+
+    let dict = PyDict::new(py);
+    dict.set_item("iterations", result.iterations)?;
+    dict.set_item("warmup", result.warmup)?;
+    dict.set_item("bytes_per_iteration", result.bytes_per_iteration)?;
+    dict.set_item("threads", result.threads)?;
+    dict.set_item("total_gbps", result.total_gbps)?;
+    dict.set_item("avg_gbps", result.total_gbps)?;
+    dict.set_item("per_core_gbps", result.per_core_gbps)?;
+    dict.set_item("min_gbps", result.min_gbps)?;
+    dict.set_item("max_gbps", result.max_gbps)?;
+    dict.set_item("stddev_gbps", result.stddev_gbps)?;
+
+    Ok(dict.into())
+}
+
 // =============================================================================
 // Module Registration
 // =============================================================================
 
 pub fn register_functions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Data layout version - record alongside seeds for long-term reproducibility
+    m.add("DATA_FORMAT_VERSION", crate::constants::DATA_FORMAT_VERSION)?;
+
     // Zero-copy buffer type
     m.add_class::<PyBytesView>()?;
 
+    // Warning category for truncated fractional ratios
+    m.add(
+        "DgenRatioTruncationWarning",
+        m.py().get_type::<DgenRatioTruncationWarning>(),
+    )?;
+
+    // Warning category for adjusted Generator chunk sizes
+    m.add(
+        "DgenChunkSizeWarning",
+        m.py().get_type::<DgenChunkSizeWarning>(),
+    )?;
+
+    // Warning category for under-parallelized Generator block sizes
+    m.add(
+        "DgenParallelismWarning",
+        m.py().get_type::<DgenParallelismWarning>(),
+    )?;
+
     // Simple API
     m.add_function(wrap_pyfunction!(generate_buffer, m)?)?;
     m.add_function(wrap_pyfunction!(generate_into_buffer, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_into_buffer_at, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_buffer, m)?)?;
+    m.add_function(wrap_pyfunction!(random_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(estimated_peak_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(py_keyed_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(fits_in_memory, m)?)?;
+    m.add_function(wrap_pyfunction!(max_safe_oneshot_size, m)?)?;
 
     // Streaming API
     m.add_class::<PyGenerator>()?;
+    m.add_class::<PyRingGenerator>()?;
+    m.add_class::<PyRingBuffer>()?;
 
     // Bulk allocation optimization
     m.add_function(wrap_pyfunction!(create_bytearrays, m)?)?;
+    m.add_function(wrap_pyfunction!(create_numa_buffers, m)?)?;
+    m.add_function(wrap_pyfunction!(create_and_generate, m)?)?;
+    m.add_function(wrap_pyfunction!(fill_buffers, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_to_file, m)?)?;
+
+    // Throughput measurement
+    m.add_function(wrap_pyfunction!(benchmark, m)?)?;
 
-    // NUMA info
-    #[cfg(feature = "numa")]
+    // NUMA info (stub returning a single UMA node without the numa feature)
     m.add_function(wrap_pyfunction!(get_numa_info, m)?)?;
 
     Ok(())
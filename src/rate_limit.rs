@@ -0,0 +1,79 @@
+// src/rate_limit.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Token-bucket pacing for `GeneratorConfig::max_gbps`
+//!
+//! The bucket holds up to `RATE_LIMIT_BURST` worth of bytes and refills at the
+//! configured rate. Each generated chunk takes its length from the bucket; when
+//! that leaves a deficit, the caller sleeps until the refill covers it. Bursts
+//! are bounded by the bucket size, and the long-run rate holds even for chunks
+//! larger than the bucket, which simply sleep longer.
+
+use std::time::{Duration, Instant};
+
+use crate::constants::RATE_LIMIT_BURST;
+
+/// Paces a byte stream to a fixed rate
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    bytes_per_sec: f64,
+    capacity: f64,
+    tokens: f64, // Negative while sleeping off a deficit
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Limiter for `gbps` gigabytes (10^9 bytes) per second, starting with a full bucket
+    pub(crate) fn new(gbps: f64) -> Self {
+        let bytes_per_sec = gbps * 1e9;
+        let capacity = bytes_per_sec * RATE_LIMIT_BURST.as_secs_f64();
+        Self {
+            bytes_per_sec,
+            capacity,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take `bytes` from the bucket, sleeping until the rate allows them
+    ///
+    /// Returns the time slept.
+    pub(crate) fn acquire(&mut self, bytes: usize) -> Duration {
+        let now = Instant::now();
+        let refill = now.duration_since(self.last_refill).as_secs_f64() * self.bytes_per_sec;
+        self.tokens = (self.tokens + refill).min(self.capacity) - bytes as f64;
+        self.last_refill = now;
+        if self.tokens >= 0.0 {
+            return Duration::ZERO;
+        }
+        // The deficit is refilled by the next call's elapsed time, sleep included
+        let wait = Duration::from_secs_f64(-self.tokens / self.bytes_per_sec);
+        std::thread::sleep(wait);
+        wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        // 10 MB/s: the bucket holds 1 MB
+        let mut limiter = RateLimiter::new(0.01);
+        let start = Instant::now();
+        assert_eq!(limiter.acquire(1_000_000), Duration::ZERO);
+        let waited = limiter.acquire(2_000_000);
+        assert!(waited >= Duration::from_millis(190), "{:?}", waited);
+        limiter.acquire(1_000_000);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(290), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+
+        // An idle period refills at most one bucket
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(limiter.acquire(900_000), Duration::ZERO);
+        assert!(limiter.acquire(500_000) >= Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,191 @@
+// src/receipt.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Reproducibility receipts for generated datasets
+//!
+//! A [`GenerationReceipt`] records everything needed to rebuild a dataset byte for
+//! byte: the configuration with its seed and block layout pinned, the data format
+//! version, the realized ratios and a checksum of the output. With the `serde`
+//! feature it serializes to JSON (or any serde format) for archiving next to the
+//! data, and [`regenerate`] turns it back into the same bytes.
+
+use std::time::Instant;
+
+use crate::block::unique_block_count;
+use crate::constants::DATA_FORMAT_VERSION;
+use crate::generator::{
+    generate_data, layout_stats, ConfigError, DataBuffer, GenerationStats, GeneratorConfig,
+};
+
+/// Portable record of one `generate_with_receipt()` call
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationReceipt {
+    /// Configuration the data was generated with, with `seed` and `format_version`
+    /// resolved and `max_threads` pinned when it picked the block size
+    pub config: GeneratorConfig,
+    /// Data layout version of the bytes (`DATA_FORMAT_VERSION` unless requested)
+    pub format_version: u32,
+    /// Realized dedup/compress ratios of the block layout, bytes and blocks
+    /// generated, and the time generation took
+    pub realized_stats: GenerationStats,
+    /// CRC-32 of the output (the same value as Python's `zlib.crc32`)
+    pub checksum: u32,
+}
+
+impl GenerationReceipt {
+    /// Whether `data` has the receipt's length and checksum
+    pub fn verify(&self, data: &[u8]) -> bool {
+        data.len() as u64 == self.realized_stats.bytes_generated
+            && crc32fast::hash(data) == self.checksum
+    }
+}
+
+/// Copy of `config` whose output no longer depends on the call or the machine
+///
+/// Resolves the seed and format version, and pins the block size chosen for the
+/// unseeded request, so recording the seed does not change the layout.
+fn pinned_config(config: GeneratorConfig) -> GeneratorConfig {
+    let mut config = config.normalized();
+    config.block_size = Some(config.auto_block_size());
+    config.seed = Some(config.call_seed());
+    config.format_version = Some(config.format_version.unwrap_or(DATA_FORMAT_VERSION));
+    config
+}
+
+/// Generate data like `generate_data()` and return a receipt that reproduces it
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{generate_with_receipt, regenerate, GeneratorConfig};
+///
+/// let (data, receipt) = generate_with_receipt(GeneratorConfig {
+///     size: 4 * 1024 * 1024,
+///     dedup_factor: 2,
+///     ..Default::default()
+/// });
+/// assert!(receipt.config.seed.is_some());
+/// assert!(receipt.verify(data.as_slice()));
+///
+/// let again = regenerate(&receipt).unwrap();
+/// assert_eq!(again.as_slice(), data.as_slice());
+/// ```
+pub fn generate_with_receipt(config: GeneratorConfig) -> (DataBuffer, GenerationReceipt) {
+    let config = pinned_config(config);
+
+    let start = Instant::now();
+    let data = generate_data(config.clone());
+    let generate_time = start.elapsed();
+
+    let block_size = config.auto_block_size();
+    let nblocks = config.aligned_size().max(block_size).div_ceil(block_size);
+    let unique_blocks = unique_block_count(nblocks, config.dedup_factor);
+    let copy_lens = config.copy_lens(unique_blocks, block_size);
+    let realized_stats = GenerationStats {
+        bytes_generated: data.len() as u64,
+        blocks_generated: nblocks as u64,
+        generate_time,
+        ..layout_stats(
+            nblocks,
+            block_size,
+            &copy_lens,
+            config.block_fill(),
+            (config.dedup_factor, config.compress_factor),
+            config.unique_prefix_bytes,
+            config.call_seed(),
+        )
+    };
+
+    let receipt = GenerationReceipt {
+        format_version: config.format_version.unwrap_or(DATA_FORMAT_VERSION),
+        checksum: crc32fast::hash(data.as_slice()),
+        realized_stats,
+        config,
+    };
+    tracing::debug!(
+        "Generation receipt: seed={:?}, format_version={}, crc32={:08x}",
+        receipt.config.seed,
+        receipt.format_version,
+        receipt.checksum
+    );
+    (data, receipt)
+}
+
+/// Regenerate the exact bytes a receipt describes
+///
+/// Fails with `ConfigError::UnsupportedFormatVersion` when the receipt was written
+/// by a build with a newer data format. Compare the result with
+/// [`GenerationReceipt::verify`] to confirm the round trip.
+pub fn regenerate(receipt: &GenerationReceipt) -> Result<DataBuffer, ConfigError> {
+    if receipt.format_version == 0 || receipt.format_version > DATA_FORMAT_VERSION {
+        return Err(ConfigError::UnsupportedFormatVersion {
+            requested: receipt.format_version,
+            current: DATA_FORMAT_VERSION,
+        });
+    }
+    Ok(generate_data(GeneratorConfig {
+        format_version: Some(receipt.format_version),
+        ..receipt.config.clone()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLOCK_SIZE;
+    use crate::content::ContentMode;
+
+    #[test]
+    fn test_receipt_regenerates_output() {
+        let config = GeneratorConfig {
+            size: BLOCK_SIZE * 6 + 4321,
+            dedup_factor: 3,
+            compress_factor: 2,
+            content: ContentMode::Csv {
+                columns: 4,
+                field_len: 8,
+            },
+            ..Default::default()
+        };
+        let (data, receipt) = generate_with_receipt(config);
+        assert_eq!(receipt.format_version, DATA_FORMAT_VERSION);
+        assert_eq!(receipt.config.block_size, Some(BLOCK_SIZE));
+        assert_eq!(receipt.realized_stats.bytes_generated, data.len() as u64);
+        assert_eq!(receipt.checksum, crc32fast::hash(data.as_slice()));
+        assert!(receipt.verify(data.as_slice()));
+
+        let mut replay = receipt.clone();
+        let again = regenerate(&replay).unwrap();
+        assert_eq!(again.as_slice(), data.as_slice());
+
+        let mut corrupted = again.as_slice().to_vec();
+        corrupted[100] ^= 1;
+        assert!(!receipt.verify(&corrupted));
+
+        replay.format_version = DATA_FORMAT_VERSION + 1;
+        assert!(matches!(
+            regenerate(&replay),
+            Err(ConfigError::UnsupportedFormatVersion { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_receipt_json_round_trip() {
+        let (data, receipt) = generate_with_receipt(GeneratorConfig {
+            size: BLOCK_SIZE * 2 + 99,
+            compress_factor: 3,
+            seed: Some(42),
+            ..Default::default()
+        });
+        let json = serde_json::to_string(&receipt).unwrap();
+        let restored: GenerationReceipt = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.checksum, receipt.checksum);
+        assert_eq!(
+            restored.realized_stats.bytes_generated,
+            receipt.realized_stats.bytes_generated
+        );
+        assert_eq!(regenerate(&restored).unwrap().as_slice(), data.as_slice());
+    }
+}
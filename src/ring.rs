@@ -0,0 +1,308 @@
+// src/ring.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Bounded ring of reusable buffers for producer/consumer pipelines
+//!
+//! A [`RingGenerator`] allocates a fixed set of page-aligned buffers once and
+//! fills them in rotation with `DataGenerator::fill_chunk()`. Each filled buffer
+//! is handed out as a [`RingBuffer`]; dropping it returns the buffer to the ring
+//! for the next fill. When every buffer is held by the consumer, the producer
+//! blocks until one comes back, so memory stays bounded at
+//! `slots * chunk_size` however far the producer could otherwise run ahead.
+
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::aligned::AlignedBuffer;
+use crate::constants::PAGE_SIZE;
+use crate::generator::{DataGenerator, GeneratorConfig};
+
+/// Buffers not currently held by a consumer
+struct FreeList {
+    buffers: Mutex<Vec<AlignedBuffer>>,
+    returned: Condvar,
+}
+
+impl FreeList {
+    fn put(&self, buffer: AlignedBuffer) {
+        self.buffers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(buffer);
+        self.returned.notify_one();
+    }
+}
+
+/// Streaming generator that fills a fixed set of reusable buffers in rotation
+///
+/// Iterating yields one [`RingBuffer`] per chunk, in stream order, until the
+/// configured size has been produced. `next()` blocks while all `slots` buffers
+/// are held; drop (or send to another thread and drop) each buffer once consumed.
+/// Holding every buffer on the iterating thread deadlocks, so keep fewer than
+/// `slots` alive across calls.
+///
+/// The concatenated chunks are identical to `DataGenerator::new(config)` output.
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{GeneratorConfig, RingBuffer, RingGenerator};
+///
+/// let config = GeneratorConfig {
+///     size: 16 * 1024 * 1024,
+///     ..Default::default()
+/// };
+/// let (tx, rx) = std::sync::mpsc::sync_channel::<RingBuffer>(2);
+/// let ring = RingGenerator::new(config, 4, 4 * 1024 * 1024);
+/// let consumer = std::thread::spawn(move || {
+///     let mut total = 0;
+///     for buf in rx {
+///         total += buf.len(); // Dropping buf frees its slot for the next fill
+///     }
+///     total
+/// });
+/// for buf in ring {
+///     tx.send(buf).unwrap();
+/// }
+/// drop(tx);
+/// assert_eq!(consumer.join().unwrap(), 16 * 1024 * 1024);
+/// ```
+pub struct RingGenerator {
+    generator: DataGenerator,
+    free: Arc<FreeList>,
+    slots: usize,
+    chunk_size: usize,
+}
+
+impl RingGenerator {
+    /// Ring of `slots` buffers of `chunk_size` bytes each generating `config`
+    ///
+    /// `chunk_size` 0 selects `DataGenerator::recommended_chunk_size()`, and any
+    /// size is capped at the stream size; `slots` is raised to at least 1. All
+    /// buffers are allocated up front.
+    pub fn new(config: GeneratorConfig, slots: usize, chunk_size: usize) -> Self {
+        let generator = DataGenerator::new(config);
+        let slots = slots.max(1);
+        let chunk_size = if chunk_size == 0 {
+            DataGenerator::recommended_chunk_size()
+        } else {
+            chunk_size
+        }
+        .min(generator.total_size().max(1));
+        let buffers = (0..slots)
+            .map(|_| AlignedBuffer::zeroed(chunk_size, PAGE_SIZE))
+            .collect();
+        Self {
+            generator,
+            free: Arc::new(FreeList {
+                buffers: Mutex::new(buffers),
+                returned: Condvar::new(),
+            }),
+            slots,
+            chunk_size,
+        }
+    }
+
+    /// Number of buffers in the ring
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Capacity of each buffer (the size of every chunk but possibly the last)
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Buffers ready to be filled (not held by a consumer)
+    pub fn free_slots(&self) -> usize {
+        self.free
+            .buffers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Wait up to `timeout` for a free buffer; true if one is available
+    ///
+    /// Lets a caller that must stay responsive (e.g. to signals) wait in slices
+    /// before calling `next_buffer()`, which then does not block.
+    pub fn wait_free(&self, timeout: Duration) -> bool {
+        let free = self.free.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        let (free, _) = self
+            .free
+            .returned
+            .wait_timeout_while(free, timeout, |buffers| buffers.is_empty())
+            .unwrap_or_else(|e| e.into_inner());
+        !free.is_empty()
+    }
+
+    /// Whether every byte has been produced
+    pub fn is_complete(&self) -> bool {
+        self.generator.is_complete()
+    }
+
+    /// The underlying generator, for `position()`, `stats()` and the like
+    pub fn generator(&self) -> &DataGenerator {
+        &self.generator
+    }
+
+    /// Wait for a free buffer and fill it with the next chunk
+    ///
+    /// Same as `Iterator::next()`; returns None once the stream is complete,
+    /// without waiting.
+    pub fn next_buffer(&mut self) -> Option<RingBuffer> {
+        if self.generator.is_complete() {
+            return None;
+        }
+        let mut buffer = {
+            let mut free = self.free.buffers.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                if let Some(buffer) = free.pop() {
+                    break buffer;
+                }
+                free = self
+                    .free
+                    .returned
+                    .wait(free)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+        };
+        let offset = self.generator.position() as u64;
+        buffer.set_len(self.chunk_size);
+        let written = self.generator.fill_chunk(&mut buffer);
+        buffer.set_len(written);
+        Some(RingBuffer {
+            buffer: Some(buffer),
+            offset,
+            free: Arc::clone(&self.free),
+        })
+    }
+}
+
+impl Iterator for RingGenerator {
+    type Item = RingBuffer;
+
+    fn next(&mut self) -> Option<RingBuffer> {
+        self.next_buffer()
+    }
+}
+
+impl std::fmt::Debug for RingGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RingGenerator")
+            .field("slots", &self.slots)
+            .field("chunk_size", &self.chunk_size)
+            .field("position", &self.generator.position())
+            .field("total_size", &self.generator.total_size())
+            .finish()
+    }
+}
+
+/// One filled chunk of a [`RingGenerator`]
+///
+/// Dereferences to the generated bytes. Dropping it returns the buffer to the
+/// ring, so the data must be copied out first if it is needed afterwards.
+pub struct RingBuffer {
+    buffer: Option<AlignedBuffer>,
+    offset: u64,
+    free: Arc<FreeList>,
+}
+
+impl RingBuffer {
+    /// Stream position of the first byte of this chunk
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl Deref for RingBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.buffer.as_deref().unwrap_or_default()
+    }
+}
+
+impl Drop for RingBuffer {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            self.free.put(buffer);
+        }
+    }
+}
+
+impl std::fmt::Debug for RingBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RingBuffer")
+            .field("offset", &self.offset)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLOCK_SIZE;
+
+    #[test]
+    fn test_ring_generator() {
+        let config = GeneratorConfig {
+            size: BLOCK_SIZE * 5 + 321,
+            dedup_factor: 2,
+            compress_factor: 3,
+            seed: Some(11),
+            ..Default::default()
+        };
+        let mut expected = vec![0u8; config.size];
+        DataGenerator::new(config.clone()).fill_chunk(&mut expected);
+
+        let chunk = BLOCK_SIZE * 2 - 7;
+        let mut ring = RingGenerator::new(config, 2, chunk);
+        assert_eq!((ring.slots(), ring.free_slots()), (2, 2));
+
+        let first = ring.next().unwrap();
+        let second = ring.next().unwrap();
+        assert_eq!(ring.free_slots(), 0);
+        assert!(!ring.wait_free(Duration::from_millis(10)));
+        assert_eq!((first.offset(), second.offset()), (0, chunk as u64));
+        assert_eq!(first.as_ptr() as usize % PAGE_SIZE, 0);
+
+        // The producer blocks until the consumer returns a buffer
+        let mut output = Vec::new();
+        let consumer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            let mut data = first.to_vec();
+            data.extend_from_slice(&second);
+            data
+        });
+        let third = ring.next().unwrap();
+        output.extend(consumer.join().unwrap());
+        assert_eq!(third.offset(), 2 * chunk as u64);
+        assert_eq!(third.len(), expected.len() - 2 * chunk);
+        output.extend_from_slice(&third);
+        assert!(ring.is_complete());
+        assert!(ring.next().is_none());
+        assert_eq!(output, expected);
+
+        drop(third);
+        assert_eq!(ring.free_slots(), 2);
+        assert!(ring.wait_free(Duration::ZERO));
+
+        // Chunks never exceed the stream
+        let ring = RingGenerator::new(
+            GeneratorConfig {
+                size: BLOCK_SIZE * 3,
+                ..Default::default()
+            },
+            0,
+            0,
+        );
+        assert_eq!((ring.slots(), ring.chunk_size()), (1, BLOCK_SIZE * 3));
+        assert_eq!(
+            ring.map(|buf| buf.len()).collect::<Vec<_>>(),
+            vec![BLOCK_SIZE * 3]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+// src/sequential.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Sequential stand-in for the rayon API used by the generator
+//!
+//! Built when the `generator` feature is enabled without `parallel`. The
+//! generator and content modules import this module as `rayon`, so the fill loops
+//! are the same code in both builds: "pools" run every closure on the calling
+//! thread and the `par_*` slice methods are the ordinary sequential iterators. No
+//! threads are ever spawned, and `max_threads` has no effect on the output, which
+//! is already independent of the thread count. `broadcast` only backs the
+//! node-local fill, which outside the `numa` feature is compiled for tests.
+
+use std::fmt;
+#[cfg(test)]
+use std::marker::PhantomData;
+
+/// Number of threads generation runs on (always the calling thread)
+pub fn current_num_threads() -> usize {
+    1
+}
+
+/// Mirrors `rayon::ThreadPoolBuilder`; the settings are accepted and ignored
+#[derive(Debug, Default)]
+pub struct ThreadPoolBuilder;
+
+impl ThreadPoolBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn num_threads(self, _num_threads: usize) -> Self {
+        self
+    }
+
+    pub fn thread_name<F>(self, _name: F) -> Self
+    where
+        F: FnMut(usize) -> String + 'static,
+    {
+        self
+    }
+
+    pub fn stack_size(self, _stack_size: usize) -> Self {
+        self
+    }
+
+    pub fn build(self) -> Result<ThreadPool, ThreadPoolBuildError> {
+        Ok(ThreadPool)
+    }
+}
+
+/// Mirrors `rayon::ThreadPoolBuildError`; never produced
+#[derive(Debug)]
+pub struct ThreadPoolBuildError;
+
+impl fmt::Display for ThreadPoolBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("thread pool unavailable")
+    }
+}
+
+impl std::error::Error for ThreadPoolBuildError {}
+
+/// A "pool" that runs work on the calling thread
+#[derive(Debug)]
+pub struct ThreadPool;
+
+impl ThreadPool {
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R,
+    {
+        op()
+    }
+
+    #[cfg(test)]
+    pub fn broadcast<OP, R>(&self, op: OP) -> Vec<R>
+    where
+        OP: Fn(BroadcastContext<'_>) -> R,
+    {
+        vec![op(BroadcastContext(PhantomData))]
+    }
+
+    pub fn current_num_threads(&self) -> usize {
+        1
+    }
+}
+
+/// Mirrors `rayon::BroadcastContext` for the single calling thread
+#[cfg(test)]
+pub struct BroadcastContext<'a>(PhantomData<&'a ()>);
+
+#[cfg(test)]
+impl BroadcastContext<'_> {
+    pub fn index(&self) -> usize {
+        0
+    }
+}
+
+pub mod prelude {
+    //! Sequential `par_*` slice methods
+
+    /// The `par_*` methods the generator calls, as plain slice iterators
+    pub trait ParallelSlice<T> {
+        fn par_iter(&self) -> std::slice::Iter<'_, T>;
+        fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T>;
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T>;
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> std::slice::ChunksExactMut<'_, T>;
+    }
+
+    impl<T> ParallelSlice<T> for [T] {
+        fn par_iter(&self) -> std::slice::Iter<'_, T> {
+            self.iter()
+        }
+
+        fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+            self.iter_mut()
+        }
+
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T> {
+            self.chunks_mut(chunk_size)
+        }
+
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> std::slice::ChunksExactMut<'_, T> {
+            self.chunks_exact_mut(chunk_size)
+        }
+    }
+}
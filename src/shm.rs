@@ -0,0 +1,250 @@
+// src/shm.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Generate directly into a POSIX shared-memory segment for zero-copy IPC
+//!
+//! On Linux, `shm_open(name)` is a file at `/dev/shm/name` on a tmpfs mount, so
+//! the segment is created and sized with ordinary file operations, mapped
+//! writable, and filled in place by the parallel generator. Other processes map
+//! the same segment read-only (`ShmHandle::open()` here, or `shm_open` +
+//! `mmap(PROT_READ)` / Python `multiprocessing.shared_memory`) without copying.
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::cpus::effective_cpu_count;
+use crate::generator::{generate_range, thread_pool_builder, GeneratorConfig};
+
+/// Mount point backing POSIX shared memory on Linux
+const SHM_DIR: &str = "/dev/shm";
+
+/// A shared-memory segment holding a generated dataset
+///
+/// Dropping the handle closes its descriptor but leaves the segment in place, as
+/// with `shm_open`: it lives until `unlink()` is called (or the system reboots),
+/// so consumers can attach after the producer exits.
+#[derive(Debug)]
+pub struct ShmHandle {
+    name: String,
+    file: File,
+    size: u64,
+}
+
+impl ShmHandle {
+    /// Open an existing segment read-only
+    ///
+    /// `name` is the segment name as passed to `generate_to_shm()`; a leading `/`
+    /// (`shm_open` style) is accepted.
+    pub fn open(name: &str) -> io::Result<Self> {
+        let name = validate_name(name)?;
+        let file = File::open(Path::new(SHM_DIR).join(name))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            name: name.to_owned(),
+            file,
+            size,
+        })
+    }
+
+    /// Segment name, without the leading `/`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Path of the segment under `/dev/shm`
+    pub fn path(&self) -> PathBuf {
+        Path::new(SHM_DIR).join(&self.name)
+    }
+
+    /// Size of the segment in bytes
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+
+    /// Whether the segment is zero bytes long
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Underlying file descriptor, e.g. to pass to another process over a socket
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Map the segment read-only into this process
+    pub fn map(&self) -> io::Result<Mmap> {
+        // SAFETY: the mapping is read-only; the segment is only written by
+        // generate_to_shm(), which finishes before it returns the handle
+        unsafe { Mmap::map(&self.file) }
+    }
+
+    /// Remove the segment name so no new process can attach
+    ///
+    /// Existing mappings stay valid until they are unmapped.
+    pub fn unlink(self) -> io::Result<()> {
+        std::fs::remove_file(self.path())
+    }
+}
+
+impl AsFd for ShmHandle {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+impl AsRawFd for ShmHandle {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+/// Create a shared-memory segment `name` and fill it with generated data
+///
+/// The segment is sized to `config.aligned_size()` and filled in place on a
+/// rayon pool of `config.max_threads` threads, so no intermediate buffer is
+/// allocated. With `config.seed` set the contents match
+/// `generate_data(config.clone())` byte-for-byte.
+///
+/// `name` follows `shm_open` rules: a single path component, optionally with a
+/// leading `/`. The segment is created with mode `0600` and must not already
+/// exist (`ErrorKind::AlreadyExists`); on any error after creation it is removed
+/// again.
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{generate_data, generate_to_shm, GeneratorConfig, ShmHandle};
+///
+/// let config = GeneratorConfig {
+///     size: 4 * 1024 * 1024,
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+/// let name = format!("dgen-doc-{}", std::process::id());
+/// let handle = generate_to_shm(&name, &config).unwrap();
+///
+/// // A consumer (normally another process) attaches read-only
+/// let reader = ShmHandle::open(&name).unwrap();
+/// let data = reader.map().unwrap();
+/// assert_eq!(&data[..], generate_data(config).as_slice());
+///
+/// handle.unlink().unwrap();
+/// ```
+pub fn generate_to_shm(name: &str, config: &GeneratorConfig) -> io::Result<ShmHandle> {
+    let name = validate_name(name)?;
+    let path = Path::new(SHM_DIR).join(name);
+    let size = config.aligned_size() as u64;
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)?;
+
+    if let Err(e) = fill_segment(&file, size, config) {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+
+    Ok(ShmHandle {
+        name: name.to_owned(),
+        file,
+        size,
+    })
+}
+
+/// Size the segment and generate the dataset into a writable mapping of it
+fn fill_segment(file: &File, size: u64, config: &GeneratorConfig) -> io::Result<()> {
+    file.set_len(size)?;
+    if size == 0 {
+        return Ok(());
+    }
+
+    // SAFETY: the segment was just created exclusively (O_EXCL) by this process,
+    // so nothing else is mapping or resizing it while it is filled
+    let mut map = unsafe { MmapMut::map_mut(file)? };
+    let num_threads = config.max_threads.unwrap_or_else(effective_cpu_count);
+
+    tracing::info!(
+        "Generating {} bytes into shared memory: dedup={}, compress={}, threads={}",
+        size,
+        config.dedup_factor,
+        config.compress_factor,
+        num_threads
+    );
+
+    let pool = thread_pool_builder(
+        num_threads,
+        config.thread_name_prefix.as_deref(),
+        config.thread_stack_size,
+    )
+    .build()
+    .map_err(io::Error::other)?;
+    pool.install(|| generate_range(config, 0, &mut map));
+
+    Ok(())
+}
+
+/// Strip an optional leading `/` and check the rest is a single path component
+fn validate_name(name: &str) -> io::Result<&str> {
+    let name = name.strip_prefix('/').unwrap_or(name);
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid shared memory name '{}'", name),
+        ));
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate_data;
+
+    #[test]
+    fn test_generate_to_shm() {
+        let config = GeneratorConfig {
+            size: 5 * 1024 * 1024 + 123,
+            dedup_factor: 2,
+            compress_factor: 3,
+            seed: Some(77),
+            max_threads: Some(2),
+            ..Default::default()
+        };
+        let name = format!("/dgen-test-{}", std::process::id());
+        let handle = generate_to_shm(&name, &config).unwrap();
+        assert_eq!(handle.len(), config.aligned_size() as u64);
+        assert!(handle.path().exists());
+
+        // Creating the same segment again must not clobber it
+        let err = generate_to_shm(&name, &config).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+        let reader = ShmHandle::open(&name).unwrap();
+        assert_eq!(reader.len(), handle.len());
+        let data = reader.map().unwrap();
+        assert_eq!(&data[..], generate_data(config).as_slice());
+
+        let path = handle.path();
+        handle.unlink().unwrap();
+        assert!(!path.exists());
+        // Existing mappings outlive the name
+        assert_eq!(data.len() as u64, reader.len());
+    }
+
+    #[test]
+    fn test_invalid_shm_name() {
+        let config = GeneratorConfig::default();
+        for name in ["", "/", "a/b", "..", "/../etc"] {
+            let err = generate_to_shm(name, &config).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// src/tokio_channel.rs
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Producer/consumer integration: stream generated chunks through a tokio channel
+
+use tokio::sync::mpsc;
+
+use crate::generator::{DataGenerator, GeneratorConfig};
+
+/// Number of chunks buffered in the channel before the producer waits
+///
+/// Small on purpose: each chunk is typically 32 MB, and a slow consumer should
+/// apply backpressure rather than let generated data pile up in memory.
+pub const CHANNEL_CAPACITY: usize = 4;
+
+/// Run a `DataGenerator` on a blocking task and receive its chunks from a channel
+///
+/// Chunks of `chunk_size` bytes (0 selects `DataGenerator::recommended_chunk_size()`;
+/// the last chunk may be shorter) are sent through a bounded channel of
+/// `CHANNEL_CAPACITY`, so generation pauses while the consumer falls behind.
+/// The channel closes once `config.size` bytes have been sent. Dropping the
+/// receiver stops the producer after its current chunk.
+///
+/// Must be called from within a tokio runtime.
+///
+/// # Example
+/// ```rust
+/// use dgen_data::{spawn_generator, GeneratorConfig};
+///
+/// # let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// # rt.block_on(async {
+/// let config = GeneratorConfig {
+///     size: 8 * 1024 * 1024,
+///     ..Default::default()
+/// };
+/// let mut rx = spawn_generator(config, 1024 * 1024);
+/// let mut total = 0;
+/// while let Some(chunk) = rx.recv().await {
+///     total += chunk.len();
+/// }
+/// assert_eq!(total, 8 * 1024 * 1024);
+/// # });
+/// ```
+pub fn spawn_generator(config: GeneratorConfig, chunk_size: usize) -> mpsc::Receiver<bytes::Bytes> {
+    let chunk_size = if chunk_size == 0 {
+        DataGenerator::recommended_chunk_size()
+    } else {
+        chunk_size
+    };
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        let mut generator = DataGenerator::new(config);
+        // Each chunk gets its own allocation since ownership moves to the consumer
+        while let Some(chunk) = generator.next_chunk_bytes(chunk_size) {
+            if tx.blocking_send(chunk).is_err() {
+                tracing::debug!(
+                    "spawn_generator: receiver dropped at position {}, stopping",
+                    generator.position()
+                );
+                return;
+            }
+        }
+        tracing::debug!(
+            "spawn_generator: sent {} bytes, closing channel",
+            generator.position()
+        );
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_spawn_generator_matches_fill_chunk() {
+        let config = GeneratorConfig {
+            size: 5 * 1024 * 1024 + 100,
+            compress_factor: 2,
+            seed: Some(5),
+            max_threads: Some(2),
+            ..Default::default()
+        };
+
+        let mut expected = Vec::new();
+        DataGenerator::new(config.clone())
+            .write_all_to(&mut expected, 1024 * 1024)
+            .unwrap();
+
+        let received = runtime().block_on(async {
+            let mut rx = spawn_generator(config, 1024 * 1024);
+            let mut received = Vec::new();
+            while let Some(chunk) = rx.recv().await {
+                assert!(chunk.len() <= 1024 * 1024);
+                received.extend_from_slice(&chunk);
+            }
+            received
+        });
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn test_spawn_generator_stops_when_receiver_dropped() {
+        let config = GeneratorConfig {
+            size: 1024 * 1024 * 1024,
+            ..Default::default()
+        };
+
+        let rt = runtime();
+        rt.block_on(async {
+            let mut rx = spawn_generator(config, 1024 * 1024);
+            assert!(rx.recv().await.is_some());
+            drop(rx);
+        });
+        // Producer exits instead of generating the remaining ~1 GB
+        rt.shutdown_timeout(std::time::Duration::from_secs(10));
+    }
+}